@@ -0,0 +1,192 @@
+use anyhow::{Context, Result};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::config;
+
+use super::setup::normalize_version;
+
+/// A downloaded SDK archive kept in ~/.mpf-sdk/cache
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct CachedArchive {
+    asset: String,
+    path: String,
+    size: u64,
+    sha256: String,
+}
+
+/// cache_manifest.json: version -> cached archives for that version
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct CacheManifest {
+    #[serde(default)]
+    versions: BTreeMap<String, Vec<CachedArchive>>,
+}
+
+fn manifest_path() -> PathBuf {
+    config::cache_dir().join("cache_manifest.json")
+}
+
+impl CacheManifest {
+    fn load() -> Self {
+        fs::read_to_string(manifest_path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = manifest_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(&path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(())
+    }
+
+    fn find(&self, version: &str, asset: &str) -> Option<&CachedArchive> {
+        self.versions
+            .get(version)
+            .and_then(|entries| entries.iter().find(|e| e.asset == asset))
+    }
+}
+
+/// Location of the cached archive for a version/asset pair
+pub(super) fn archive_path(version: &str, asset: &str) -> PathBuf {
+    config::cache_dir().join(format!("{}-{}", version, asset))
+}
+
+/// Check whether a usable cached archive exists.
+///
+/// The file must exist, match the expected size (from Content-Length, when
+/// known) and match the SHA-256 digest recorded when it was downloaded.
+pub(super) fn is_cached(version: &str, asset: &str, expected_size: u64) -> bool {
+    let manifest = CacheManifest::load();
+    let entry = match manifest.find(version, asset) {
+        Some(e) => e,
+        None => return false,
+    };
+    let path = Path::new(&entry.path);
+    let size = match fs::metadata(path) {
+        Ok(m) => m.len(),
+        Err(_) => return false,
+    };
+    if size != entry.size || (expected_size != 0 && size != expected_size) {
+        return false;
+    }
+    sha256_file(path)
+        .map(|digest| digest == entry.sha256)
+        .unwrap_or(false)
+}
+
+/// Record a freshly downloaded archive in the manifest
+pub(super) fn record(version: &str, asset: &str, path: &Path) -> Result<()> {
+    let size = fs::metadata(path)?.len();
+    let sha256 = sha256_file(path)?;
+
+    let mut manifest = CacheManifest::load();
+    let entries = manifest.versions.entry(version.to_string()).or_default();
+    entries.retain(|e| e.asset != asset);
+    entries.push(CachedArchive {
+        asset: asset.to_string(),
+        path: path.to_string_lossy().to_string(),
+        size,
+        sha256,
+    });
+    manifest.save()
+}
+
+fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = File::open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+pub(super) fn format_size(bytes: u64) -> String {
+    const MB: f64 = 1024.0 * 1024.0;
+    format!("{:.1} MB", bytes as f64 / MB)
+}
+
+/// Cache list command: show cached SDK archives
+pub fn cache_list() -> Result<()> {
+    let manifest = CacheManifest::load();
+
+    if manifest.versions.is_empty() {
+        println!("No cached SDK archives.");
+        return Ok(());
+    }
+
+    println!("{}", "Cached SDK archives:".bold());
+    let mut total = 0u64;
+    for (version, entries) in &manifest.versions {
+        for entry in entries {
+            let exists = Path::new(&entry.path).exists();
+            let marker = if exists {
+                "✓".green()
+            } else {
+                "✗".red()
+            };
+            println!(
+                "  {} {} {} ({})",
+                marker,
+                version.green(),
+                entry.asset,
+                format_size(entry.size)
+            );
+            println!("      sha256: {}", entry.sha256.dimmed());
+            if exists {
+                total += entry.size;
+            }
+        }
+    }
+    println!();
+    println!("  Total: {}", format_size(total));
+    println!("  Location: {}", config::cache_dir().display());
+
+    Ok(())
+}
+
+/// Cache clear command: remove cached archives (all, or one version)
+pub fn cache_clear(version: Option<String>) -> Result<()> {
+    let mut manifest = CacheManifest::load();
+
+    let targets: Vec<String> = match &version {
+        Some(v) => {
+            let v = normalize_version(v);
+            if !manifest.versions.contains_key(&v) {
+                println!("{} No cached archives for {}", "Note:".yellow(), v);
+                return Ok(());
+            }
+            vec![v]
+        }
+        None => manifest.versions.keys().cloned().collect(),
+    };
+
+    let mut removed = 0u32;
+    for v in &targets {
+        if let Some(entries) = manifest.versions.remove(v) {
+            for entry in entries {
+                if fs::remove_file(&entry.path).is_ok() {
+                    removed += 1;
+                }
+                println!("  - {} {}", v, entry.asset);
+            }
+        }
+    }
+    manifest.save()?;
+
+    println!("{} Removed {} cached archive(s)", "✓".green(), removed);
+    Ok(())
+}
@@ -0,0 +1,380 @@
+use anyhow::{bail, Context, Result};
+use colored::*;
+use std::collections::BTreeMap;
+use std::fs;
+
+use crate::config::migration::{describe_changes, flatten, migrate_config, schema_version};
+use crate::config::{dev_config_path, DevConfig, CURRENT_SCHEMA_VERSION};
+
+use super::debug_info::mask;
+use super::init::{is_managed_cmake_var, reinit_all};
+
+/// Map fields of dev.json and how many key segments after them are map keys
+/// (kept verbatim) rather than field names
+const MAP_FIELDS: &[(&str, usize)] = &[
+    ("components", 1),
+    ("extra_cmake_vars", 1),
+    ("version_aliases", 1),
+    ("environments", 2),
+];
+
+/// Split a dotted key into JSON object keys: field names accept `-` for `_`
+/// (`post-link-hook`), map keys such as component names are kept as typed
+fn config_path(key: &str) -> Vec<String> {
+    let mut path = Vec::new();
+    let mut verbatim = 0;
+    for segment in key.split('.') {
+        if verbatim > 0 {
+            path.push(segment.to_string());
+            verbatim -= 1;
+            continue;
+        }
+        let field = segment.replace('-', "_");
+        if path.is_empty() {
+            verbatim = MAP_FIELDS
+                .iter()
+                .find(|(name, _)| *name == field)
+                .map_or(0, |(_, n)| *n);
+        }
+        path.push(field);
+    }
+    path
+}
+
+fn lookup<'a>(value: &'a serde_json::Value, path: &[String]) -> Option<&'a serde_json::Value> {
+    path.iter()
+        .try_fold(value, |current, segment| current.get(segment))
+        .filter(|v| !v.is_null())
+}
+
+/// The object holding the last segment of `path`, creating empty maps on the way
+fn parent_object<'a>(
+    root: &'a mut serde_json::Value,
+    path: &[String],
+) -> Result<&'a mut serde_json::Map<String, serde_json::Value>> {
+    let mut current = root;
+    for (i, segment) in path[..path.len() - 1].iter().enumerate() {
+        let object = current
+            .as_object_mut()
+            .with_context(|| format!("'{}' is not an object", path[..i].join(".")))?;
+        if i == 1 && path[0] == "components" && !object.contains_key(segment) {
+            bail!("Component '{}' is not linked", segment);
+        }
+        current = object
+            .entry(segment.clone())
+            .or_insert_with(|| serde_json::Value::Object(Default::default()));
+    }
+    current
+        .as_object_mut()
+        .with_context(|| format!("'{}' is not an object", path[..path.len() - 1].join(".")))
+}
+
+/// Read a dev.json value by dotted key (`components.http-client.lib`).
+/// Strings come back as-is, other values as JSON.
+fn get_config_field(config: &DevConfig, key: &str) -> Result<String> {
+    let value = serde_json::to_value(config)?;
+    match lookup(&value, &config_path(key)) {
+        Some(serde_json::Value::String(s)) => Ok(s.clone()),
+        Some(other) => Ok(serde_json::to_string_pretty(other)?),
+        None => bail!("'{}' is not set", key),
+    }
+}
+
+/// Change a dev.json value by dotted key. The value is parsed as JSON when
+/// that fits the field (numbers, lists), else taken as a string; an empty
+/// value clears the setting.
+fn set_config_field(config: &mut DevConfig, key: &str, value: &str) -> Result<()> {
+    let path = config_path(key);
+    let base = serde_json::to_value(&*config)?;
+
+    if value.trim().is_empty() {
+        let mut root = base;
+        let last = path.last().context("Empty setting key")?;
+        parent_object(&mut root, &path)?.remove(last);
+        *config = serde_json::from_value(root)
+            .with_context(|| format!("'{}' can't be cleared", key))?;
+        return Ok(());
+    }
+
+    let mut candidates: Vec<serde_json::Value> = serde_json::from_str(value).into_iter().collect();
+    candidates.push(serde_json::Value::String(value.to_string()));
+
+    let mut error = None;
+    for candidate in candidates {
+        let mut root = base.clone();
+        let last = path.last().context("Empty setting key")?;
+        parent_object(&mut root, &path)?.insert(last.clone(), candidate.clone());
+        match serde_json::from_value::<DevConfig>(root) {
+            Ok(updated) => {
+                // Unknown fields are dropped by serde, so check the value stuck
+                if lookup(&serde_json::to_value(&updated)?, &path) != Some(&candidate) {
+                    bail!("Unknown setting '{}'", key);
+                }
+                *config = updated;
+                return Ok(());
+            }
+            Err(e) => error = Some(e),
+        }
+    }
+    match error {
+        Some(e) => bail!("Invalid value '{}' for '{}': {}", value, key, e),
+        None => bail!("Invalid value '{}' for '{}'", value, key),
+    }
+}
+
+/// Config get command: print one dev.json value
+pub fn config_get(key: &str) -> Result<()> {
+    let dev_config = DevConfig::load()?;
+    println!("{}", get_config_field(&dev_config, key)?);
+    Ok(())
+}
+
+/// Config list command: print every dev.json setting as a dotted key
+pub fn config_list() -> Result<()> {
+    let dev_config = DevConfig::load()?;
+    let mut entries = BTreeMap::new();
+    flatten(&serde_json::to_value(&dev_config)?, "", &mut entries);
+    for (key, value) in entries {
+        let value = match value {
+            serde_json::Value::String(s) => s,
+            other => other.to_string(),
+        };
+        // Don't echo secrets back to the terminal
+        println!("{} = {}", key.cyan(), mask(&key, &value));
+    }
+    Ok(())
+}
+
+/// Config set command: update a setting in dev.json
+pub fn config_set(key: &str, value: &str) -> Result<()> {
+    let mut dev_config = DevConfig::load()?;
+    let path = config_path(key);
+    let cleared = value.trim().is_empty();
+
+    if path == ["project_root_search_depth"] && !cleared {
+        match value.parse::<u32>() {
+            Ok(depth) if depth > 0 => {}
+            _ => bail!("project-root-search-depth must be a positive number, got '{}'", value),
+        }
+    }
+
+    set_config_field(&mut dev_config, key, value)?;
+    dev_config.save()?;
+
+    if path == ["github_token"] && !cleared {
+        println!(
+            "{} token stored in plaintext in {}",
+            "Warning:".yellow(),
+            dev_config_path().display()
+        );
+    }
+
+    if cleared {
+        println!("{} {} cleared", "✓".green(), key.cyan());
+    } else if mask(key, value) != value {
+        println!("{} {} set", "✓".green(), key.cyan());
+    } else {
+        println!("{} {} = {}", "✓".green(), key.cyan(), value);
+    }
+
+    // Component settings and CMake variables end up in the generated presets
+    if matches!(path[0].as_str(), "components" | "extra_cmake_vars") {
+        reinit_all(&mut dev_config)?;
+    }
+    Ok(())
+}
+
+/// Config cmake-var set: add an extra CMake cache variable to the presets
+pub fn cmake_var_set(key: &str, value: &str) -> Result<()> {
+    if is_managed_cmake_var(key) {
+        println!(
+            "{} {} is managed by mpf-dev; the computed value takes priority over this one",
+            "Warning:".yellow(),
+            key
+        );
+    }
+
+    let mut dev_config = DevConfig::load()?;
+    dev_config
+        .extra_cmake_vars
+        .insert(key.to_string(), value.to_string());
+    dev_config.save()?;
+    println!("{} {} = {}", "✓".green(), key.cyan(), value);
+
+    reinit_all(&mut dev_config)
+}
+
+/// Config cmake-var unset: remove an extra CMake cache variable
+pub fn cmake_var_unset(key: &str) -> Result<()> {
+    let mut dev_config = DevConfig::load()?;
+    if dev_config.extra_cmake_vars.remove(key).is_none() {
+        println!("{} {} is not set", "Note:".yellow(), key);
+        return Ok(());
+    }
+    dev_config.save()?;
+    println!("{} {} removed", "✓".green(), key.cyan());
+
+    reinit_all(&mut dev_config)
+}
+
+/// Config clear-cache: drop the cached Qt/compiler detection results
+pub fn config_clear_cache() -> Result<()> {
+    let mut dev_config = DevConfig::load()?;
+    if dev_config.compiler_cache_expiry.is_none() {
+        println!("{} No cached toolchain paths", "Note:".yellow());
+        return Ok(());
+    }
+    dev_config.clear_detection_cache();
+    dev_config.save()?;
+    println!(
+        "{} Toolchain cache cleared; Qt and compilers are detected again on next use",
+        "✓".green()
+    );
+    Ok(())
+}
+
+/// Migrate command: show and apply the upgrade of dev.json to the current schema
+pub fn migrate(dry_run: bool) -> Result<()> {
+    let path = dev_config_path();
+    if !path.exists() {
+        println!("No dev.json at {}; nothing to migrate.", path.display());
+        return Ok(());
+    }
+
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let old: serde_json::Value =
+        serde_json::from_str(&content).with_context(|| "Failed to parse dev.json")?;
+    let version = schema_version(&old);
+    if version >= CURRENT_SCHEMA_VERSION {
+        println!(
+            "{} dev.json is up to date (schema version {})",
+            "✓".green(),
+            version
+        );
+        return Ok(());
+    }
+
+    let config = migrate_config(old.clone(), version)?;
+    let new = serde_json::to_value(&config)?;
+    println!(
+        "Migrating dev.json from schema version {} to {}:",
+        version, CURRENT_SCHEMA_VERSION
+    );
+    for change in describe_changes(&old, &new) {
+        println!("  {}", change);
+    }
+
+    if dry_run {
+        println!();
+        println!("{}", "Dry run: dev.json was not changed.".dimmed());
+        return Ok(());
+    }
+    config.save()?;
+    println!();
+    println!("{} Migrated {}", "✓".green(), path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ComponentConfig, ComponentMode};
+
+    fn config_with(name: &str) -> DevConfig {
+        let mut config = DevConfig::default();
+        let comp = ComponentConfig {
+            mode: ComponentMode::Source,
+            ..Default::default()
+        };
+        config.components.insert(name.to_string(), comp);
+        config
+    }
+
+    #[test]
+    fn path_keeps_map_keys_and_rewrites_field_names() {
+        assert_eq!(
+            config_path("components.http-client.post-link-hook"),
+            ["components", "http-client", "post_link_hook"]
+        );
+        assert_eq!(
+            config_path("environments.my-env.http-client.cmake-config"),
+            ["environments", "my-env", "http-client", "cmake_config"]
+        );
+        assert_eq!(config_path("extra-cmake-vars.MY-VAR"), ["extra_cmake_vars", "MY-VAR"]);
+    }
+
+    #[test]
+    fn component_field() {
+        let mut config = config_with("http-client");
+        set_config_field(&mut config, "components.http-client.lib", "/build/lib").unwrap();
+        assert_eq!(config.components["http-client"].lib.as_deref(), Some("/build/lib"));
+        assert_eq!(
+            get_config_field(&config, "components.http-client.lib").unwrap(),
+            "/build/lib"
+        );
+
+        let err = set_config_field(&mut config, "components.nope.lib", "/x").unwrap_err();
+        assert!(err.to_string().contains("Component 'nope' is not linked"));
+    }
+
+    #[test]
+    fn environment_override_field() {
+        let mut config = DevConfig::default();
+        set_config_field(&mut config, "environments.staging.host", r#"{"mode": "source"}"#)
+            .unwrap();
+        set_config_field(&mut config, "environments.staging.host.bin", "/opt/host/bin").unwrap();
+        assert_eq!(
+            config.environments["staging"]["host"].bin.as_deref(),
+            Some("/opt/host/bin")
+        );
+        assert_eq!(
+            get_config_field(&config, "environments.staging.host.bin").unwrap(),
+            "/opt/host/bin"
+        );
+    }
+
+    #[test]
+    fn top_level_hook_with_dashes() {
+        let mut config = DevConfig::default();
+        set_config_field(&mut config, "post-link-hook", "make sync").unwrap();
+        assert_eq!(config.post_link_hook.as_deref(), Some("make sync"));
+        assert_eq!(get_config_field(&config, "post_link_hook").unwrap(), "make sync");
+    }
+
+    #[test]
+    fn numeric_and_list_values() {
+        let mut config = config_with("host");
+        set_config_field(&mut config, "project-root-search-depth", "5").unwrap();
+        assert_eq!(config.project_root_search_depth, Some(5));
+
+        set_config_field(&mut config, "components.host.roots", r#"["/a", "/b"]"#).unwrap();
+        assert_eq!(config.components["host"].roots, ["/a", "/b"]);
+
+        // Valid JSON of the wrong type falls back to a string
+        set_config_field(&mut config, "project-root-marker", "5").unwrap();
+        assert_eq!(config.project_root_marker.as_deref(), Some("5"));
+
+        let err = set_config_field(&mut config, "project-root-search-depth", "deep").unwrap_err();
+        assert!(err.to_string().contains("Invalid value 'deep'"));
+    }
+
+    #[test]
+    fn empty_value_clears() {
+        let mut config = DevConfig::default();
+        set_config_field(&mut config, "post-link-hook", "make sync").unwrap();
+        set_config_field(&mut config, "post-link-hook", "").unwrap();
+        assert_eq!(config.post_link_hook, None);
+        assert!(get_config_field(&config, "post-link-hook").is_err());
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        let mut config = config_with("host");
+        let err = set_config_field(&mut config, "bogus-key", "1").unwrap_err();
+        assert!(err.to_string().contains("Unknown setting 'bogus-key'"));
+        let err = set_config_field(&mut config, "components.host.bogus", "1").unwrap_err();
+        assert!(err.to_string().contains("Unknown setting"));
+        assert!(get_config_field(&config, "bogus-key").is_err());
+    }
+}
@@ -0,0 +1,203 @@
+use anyhow::Result;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::config::{self, DevConfig};
+
+use super::{detect_compilers, detect_qt_path};
+
+/// Environment variables worth including in a bug report
+const ENV_PREFIXES: &[&str] = &["MPF_", "QT_", "QML", "CMAKE_", "AQTINSTALL_", "SCCACHE_"];
+const ENV_NAMES: &[&str] = &[
+    "PATH",
+    "LD_LIBRARY_PATH",
+    "DYLD_LIBRARY_PATH",
+    "DYLD_FALLBACK_LIBRARY_PATH",
+    "VCPKG_ROOT",
+    "CONAN_HOME",
+    "CC",
+    "CXX",
+    "DISPLAY",
+    "WAYLAND_DISPLAY",
+];
+
+/// Words that mark a value as sensitive
+const SECRET_WORDS: &[&str] = &["password", "token", "secret"];
+
+/// Replace a value with `***` when its key or the value itself looks sensitive
+pub(super) fn mask(key: &str, value: &str) -> String {
+    let key = key.to_lowercase();
+    let lower = value.to_lowercase();
+    if SECRET_WORDS
+        .iter()
+        .any(|w| key.contains(w) || lower.contains(w))
+    {
+        "***".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Mask the value of a `"key": value` line, keeping the key and trailing comma
+fn mask_json_line(line: &str) -> String {
+    match line.split_once(':') {
+        Some((key, value)) if mask(key, value) != value => {
+            let comma = if value.trim_end().ends_with(',') { "," } else { "" };
+            format!("{}: \"***\"{}", key, comma)
+        }
+        _ => line.to_string(),
+    }
+}
+
+/// Qt version from the Qt6Core CMake package under a Qt prefix
+fn qt_version(qt_path: &str) -> Option<String> {
+    let file = Path::new(qt_path)
+        .join("lib")
+        .join("cmake")
+        .join("Qt6Core")
+        .join("Qt6CoreConfigVersion.cmake");
+    let content = fs::read_to_string(file).ok()?;
+    content.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix("set(PACKAGE_VERSION \"")
+            .and_then(|rest| rest.strip_suffix("\")"))
+            .map(str::to_string)
+    })
+}
+
+/// First line of `<compiler> --version`
+fn compiler_version(compiler: &str) -> Option<String> {
+    let output = Command::new(compiler).arg("--version").output().ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .map(|l| l.trim().to_string())
+}
+
+fn path_note(path: &str) -> &'static str {
+    if Path::new(path).exists() {
+        ""
+    } else {
+        " **(missing)**"
+    }
+}
+
+/// Debug-info command: print diagnostics as Markdown for bug reports
+pub fn debug_info() -> Result<()> {
+    let dev_config = DevConfig::load()?;
+
+    println!("## mpf-dev debug info");
+    println!();
+    println!("- mpf-dev: {}", env!("CARGO_PKG_VERSION"));
+    println!("- OS: {} ({})", env::consts::OS, env::consts::ARCH);
+    println!(
+        "- SDK version: {}",
+        config::current_version().unwrap_or_else(|| "not set".to_string())
+    );
+    let sdk_root = config::sdk_root().to_string_lossy().to_string();
+    println!("- SDK root: `{}`{}", sdk_root, path_note(&sdk_root));
+
+    println!();
+    println!("### Toolchain");
+    println!();
+    match detect_qt_path() {
+        Some(qt) => {
+            let version = qt_version(&qt).unwrap_or_else(|| "unknown version".to_string());
+            println!("- Qt: `{}` ({})", qt, version);
+            match detect_compilers(&qt) {
+                Some((gcc, gpp)) => {
+                    println!("- C compiler: `{}`", gcc);
+                    println!("- C++ compiler: `{}`", gpp);
+                    if let Some(version) = compiler_version(&gpp) {
+                        println!("- Compiler version: {}", version);
+                    }
+                }
+                None => println!("- Compilers: not found"),
+            }
+        }
+        None => println!("- Qt: not found"),
+    }
+
+    println!();
+    println!("### dev.json");
+    println!();
+    println!("`{}`", config::dev_config_path().display());
+    println!();
+    if let serde_json::Value::Object(fields) = serde_json::to_value(&dev_config)? {
+        for (key, value) in &fields {
+            if key == "components" {
+                continue;
+            }
+            let text = match value {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            println!("- {}: {}", key, mask(key, &text));
+        }
+    }
+
+    let mut names: Vec<&String> = dev_config.components.keys().collect();
+    names.sort();
+    for name in names {
+        let comp = &dev_config.components[name];
+        println!();
+        println!("#### {}", name);
+        println!();
+        if let serde_json::Value::Object(fields) = serde_json::to_value(comp)? {
+            for (key, value) in &fields {
+                let text = match value {
+                    serde_json::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                let is_path = matches!(
+                    key.as_str(),
+                    "lib" | "qml" | "plugin" | "headers" | "bin" | "root"
+                );
+                let note = if is_path { path_note(&text) } else { "" };
+                println!("- {}: {}{}", key, mask(key, &text), note);
+            }
+        }
+    }
+
+    println!();
+    println!("### Environment");
+    println!();
+    println!("```");
+    let mut vars: Vec<(String, String)> = env::vars()
+        .filter(|(key, _)| {
+            ENV_NAMES.contains(&key.as_str()) || ENV_PREFIXES.iter().any(|p| key.starts_with(p))
+        })
+        .collect();
+    vars.sort();
+    for (key, value) in &vars {
+        println!("{}={}", key, mask(key, value));
+    }
+    println!("```");
+
+    let mut roots: Vec<&str> = dev_config
+        .components
+        .values()
+        .flat_map(|c| c.all_roots().map(String::as_str))
+        .collect();
+    roots.sort();
+    roots.dedup();
+    for root in roots {
+        let presets = Path::new(root).join("CMakeUserPresets.json");
+        let content = match fs::read_to_string(&presets) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+        println!();
+        println!("### {}", presets.display());
+        println!();
+        println!("```json");
+        for line in content.lines() {
+            println!("{}", mask_json_line(line));
+        }
+        println!("```");
+    }
+
+    Ok(())
+}
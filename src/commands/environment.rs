@@ -0,0 +1,95 @@
+use anyhow::{bail, Result};
+use colored::*;
+use std::fs;
+
+use crate::config::{self, DevConfig};
+
+use super::init::reinit_all;
+
+/// Env-create command: add an empty (or copied) set of component overrides
+pub fn env_create(name: &str, copy: bool) -> Result<()> {
+    let mut dev_config = DevConfig::load()?;
+    if dev_config.environments.contains_key(name) {
+        bail!("Environment '{}' already exists", name);
+    }
+
+    let overrides = if copy {
+        dev_config.components.clone()
+    } else {
+        Default::default()
+    };
+    let count = overrides.len();
+    dev_config.environments.insert(name.to_string(), overrides);
+    dev_config.save()?;
+
+    let env_file = config::environment_env_file(name);
+    if !env_file.exists() {
+        if let Some(parent) = env_file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(
+            &env_file,
+            format!(
+                "# Variables loaded by `mpf-dev run` while environment '{}' is active\n",
+                name
+            ),
+        )?;
+    }
+
+    println!("{} Created environment '{}'", "✓".green(), name);
+    if copy {
+        println!("  Copied {} component(s) from the base configuration", count);
+    }
+    println!(
+        "  Overrides: {} → environments.{}",
+        config::dev_config_path().display(),
+        name
+    );
+    println!("  Env file:  {}", env_file.display());
+    println!("  Activate with: mpf-dev env-switch {}", name);
+    Ok(())
+}
+
+/// Env-switch command: activate an environment, or the base configuration
+/// when no name is given
+pub fn env_switch(name: Option<&str>) -> Result<()> {
+    let mut dev_config = DevConfig::load()?;
+
+    if let Some(name) = name {
+        if !dev_config.environments.contains_key(name) {
+            let mut known: Vec<&String> = dev_config.environments.keys().collect();
+            known.sort();
+            let known: Vec<&str> = known.iter().map(|s| s.as_str()).collect();
+            if known.is_empty() {
+                bail!(
+                    "Unknown environment '{}'. Create it with `mpf-dev env-create {}`",
+                    name,
+                    name
+                );
+            }
+            bail!(
+                "Unknown environment '{}' (available: {})",
+                name,
+                known.join(", ")
+            );
+        }
+    }
+
+    if dev_config.active_environment.as_deref() == name {
+        println!(
+            "{} Environment '{}' is already active",
+            "Note:".yellow(),
+            name.unwrap_or("base")
+        );
+        return Ok(());
+    }
+
+    dev_config.active_environment = name.map(str::to_string);
+    dev_config.save()?;
+
+    match name {
+        Some(name) => println!("{} Switched to environment '{}'", "✓".green(), name),
+        None => println!("{} Switched to the base configuration", "✓".green()),
+    }
+    reinit_all(&mut dev_config)
+}
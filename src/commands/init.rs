@@ -3,64 +3,258 @@ use colored::*;
 use std::env;
 use std::fs;
 
-use crate::config::{self, ComponentMode, DevConfig};
+use crate::config::{self, CompilerCache, ComponentMode, DevConfig};
 
 use super::{
-    component_cmake_dir_var, detect_mingw_path, detect_qt_path, normalize_path,
+    component_cmake_dir_var, detect_compilers, COMPONENT_CMAKE_DIR_VARS, detect_qt_path, infer_build_system,
+    is_multi_config, is_wsl2, normalize_path, wsl2_to_windows_path, BuildSystem,
 };
 
-/// Generate CMakeUserPresets.json for a project directory.
-///
-/// Pure logic — no interactive output. Only writes the preset file;
-/// does NOT touch the build directory.
+/// Name of the project's base configure preset for the user presets to inherit.
 ///
-/// Returns Ok(true) if generated, Ok(false) if skipped (no CMakeLists.txt).
-fn generate_user_presets(
-    project_dir: &std::path::Path,
-    dev_config: &DevConfig,
-    qt_path_fwd: &str,
-    gcc: &str,
-    gpp: &str,
-) -> Result<bool> {
-    // Skip if not a CMake project
-    if !project_dir.join("CMakeLists.txt").exists() {
-        return Ok(false);
+/// Uses the first `hidden: true` configure preset in CMakePresets.json,
+/// else the first one without a `binaryDir`, or None when neither exists.
+/// Projects without a CMakePresets.json keep the historical `base` name.
+fn detect_base_preset(project_dir: &std::path::Path) -> Option<String> {
+    let path = project_dir.join("CMakePresets.json");
+    let content = match fs::read_to_string(&path) {
+        Ok(c) => c,
+        Err(_) => return Some("base".to_string()),
+    };
+    let presets: serde_json::Value = serde_json::from_str(&content).ok()?;
+    let configure = presets.get("configurePresets")?.as_array()?;
+    configure
+        .iter()
+        .find(|p| p.get("hidden").and_then(|h| h.as_bool()) == Some(true))
+        .or_else(|| configure.iter().find(|p| p.get("binaryDir").is_none()))
+        .and_then(|p| p.get("name")?.as_str())
+        .map(String::from)
+}
+
+/// Toolchain choices baked into the generated user presets
+#[derive(Default)]
+struct PresetOptions {
+    /// Preset `generator` field (omitted when None)
+    generator: Option<String>,
+    /// Leave CMAKE_C_COMPILER / CMAKE_CXX_COMPILER to the project
+    no_compiler: bool,
+    /// CMAKE_TOOLCHAIN_FILE, used instead of the detected compilers
+    toolchain_file: Option<String>,
+    /// Platform ("windows", "linux", "macos") the presets are limited to
+    /// through a CMake `condition`
+    condition_platform: Option<String>,
+    /// Per-platform preset names, keeping other platforms' presets in the file
+    multi_platform: bool,
+}
+
+/// Platforms `--condition-platform` / `--multi-platform` know, with their
+/// CMake `${hostSystemName}`
+const PRESET_PLATFORMS: &[(&str, &str)] =
+    &[("windows", "Windows"), ("linux", "Linux"), ("macos", "Darwin")];
+
+/// This machine's entry in PRESET_PLATFORMS
+fn current_platform() -> &'static str {
+    if cfg!(windows) {
+        "windows"
+    } else if cfg!(target_os = "macos") {
+        "macos"
+    } else {
+        "linux"
+    }
+}
+
+fn host_system_name(platform: &str) -> Option<&'static str> {
+    PRESET_PLATFORMS
+        .iter()
+        .find(|(p, _)| *p == platform)
+        .map(|(_, system)| *system)
+}
+
+/// Preset `condition` that is true only on the given `${hostSystemName}`
+fn platform_condition(system: &str) -> serde_json::Value {
+    serde_json::json!({"type": "equals", "lhs": "${hostSystemName}", "rhs": system})
+}
+
+/// Name of this machine's dev preset: `dev`, or `dev-<platform>` in
+/// multi-platform files
+fn is_dev_preset(name: &str) -> bool {
+    name == "dev" || name == format!("dev-{}", current_platform())
+}
+
+impl PresetOptions {
+    /// Recover the options from an existing CMakeUserPresets.json, so that
+    /// regenerating presets after link/unlink keeps the user's choices.
+    fn from_existing(project_dir: &std::path::Path) -> Self {
+        let presets: Option<serde_json::Value> =
+            fs::read_to_string(project_dir.join("CMakeUserPresets.json"))
+                .ok()
+                .and_then(|c| serde_json::from_str(&c).ok());
+        let dev = presets.as_ref().and_then(|p| {
+            p.get("configurePresets")?
+                .as_array()?
+                .iter()
+                .find(|c| c.get("name").and_then(|n| n.as_str()).is_some_and(is_dev_preset))
+        });
+        let dev = match dev {
+            Some(d) => d,
+            None => return Self::default(),
+        };
+        let cache = dev.get("cacheVariables");
+        let cache_str = |key: &str| {
+            cache
+                .and_then(|c| c.get(key))
+                .and_then(|v| v.as_str())
+                .map(String::from)
+        };
+
+        let multi_platform = dev.get("name").and_then(|n| n.as_str()) != Some("dev");
+        let condition_platform = dev
+            .pointer("/condition/rhs")
+            .and_then(|rhs| rhs.as_str())
+            .and_then(|rhs| PRESET_PLATFORMS.iter().find(|(_, system)| *system == rhs))
+            .filter(|_| !multi_platform)
+            .map(|(platform, _)| platform.to_string());
+
+        Self {
+            generator: dev.get("generator").and_then(|g| g.as_str()).map(String::from),
+            no_compiler: cache_str("CMAKE_CXX_COMPILER").is_none(),
+            toolchain_file: cache_str("CMAKE_TOOLCHAIN_FILE"),
+            condition_platform,
+            multi_platform,
+        }
     }
+}
+
+/// Whether mpf-dev computes this cache variable itself, so a value from
+/// `extra_cmake_vars` would be ignored
+pub(super) fn is_managed_cmake_var(key: &str) -> bool {
+    const MANAGED: &[&str] = &[
+        "CMAKE_BUILD_TYPE",
+        "CMAKE_C_COMPILER",
+        "CMAKE_CXX_COMPILER",
+        "CMAKE_C_COMPILER_LAUNCHER",
+        "CMAKE_CXX_COMPILER_LAUNCHER",
+        "CMAKE_TOOLCHAIN_FILE",
+        "CMAKE_PREFIX_PATH",
+        "CMAKE_EXPORT_COMPILE_COMMANDS",
+        "QML_IMPORT_PATH",
+        "MPF_DIR",
+    ];
+    MANAGED.contains(&key) || COMPONENT_CMAKE_DIR_VARS.iter().any(|(_, var)| *var == key)
+}
+
+/// Whether a compiler cache program is on PATH
+fn compiler_cache_available(cache: CompilerCache) -> bool {
+    let finder = if cfg!(windows) { "where" } else { "which" };
+    std::process::Command::new(finder)
+        .arg(cache.program())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+/// Result of generating the dev build config (CMakeUserPresets.json,
+/// mpf-dev.pri or meson.native) for one project
+#[derive(PartialEq)]
+enum PresetOutcome {
+    /// File written with new content
+    Written,
+    /// Existing file already had identical content
+    Unchanged,
+    /// No CMakeLists.txt, *.pro or meson.build in the project root
+    Unsupported,
+}
+
+/// Options for `mpf-dev init`
+pub struct InitOptions {
+    /// Delete the whole build/ directory instead of only the CMake cache
+    pub clean: bool,
+    /// CMake generator for the generated presets
+    pub generator: Option<String>,
+    /// Also write a .clangd file
+    pub clangd: bool,
+    /// Also write a .clang-tidy file
+    pub clang_tidy: bool,
+    /// Also write IDE config: vscode, clion or qtcreator
+    pub ide: Option<String>,
+    /// Don't inject CMAKE_C_COMPILER / CMAKE_CXX_COMPILER
+    pub no_compiler: bool,
+    /// Inject CMAKE_TOOLCHAIN_FILE instead of the detected compilers
+    pub toolchain_file: Option<String>,
+    /// Rewrite presets and clear the cache even if nothing changed
+    pub force: bool,
+    /// Ignore the cached Qt/compiler paths and detect them again
+    pub refresh_cache: bool,
+    /// Enable a compiler cache (remembered in dev.json)
+    pub compiler_cache: Option<CompilerCache>,
+    /// Limit the presets to one host platform with a CMake `condition`
+    pub condition_platform: Option<String>,
+    /// Per-platform presets (dev-windows, dev-linux, ...) in one file
+    pub multi_platform: bool,
+}
+
+/// MPF_DIR for an SDK linked in source mode, so find_package(MPF) can't
+/// pick up the installed SDK first: `<install>/lib/cmake/MPF`, or the
+/// `cmake_config` directory when it holds MPFConfig.cmake itself
+fn sdk_package_dir(dev_config: &DevConfig) -> Option<String> {
+    let sdk = dev_config.source_component("sdk")?;
+    let dir = match &sdk.cmake_config {
+        Some(dir) if std::path::Path::new(dir).join("MPFConfig.cmake").is_file() => {
+            std::path::PathBuf::from(dir)
+        }
+        Some(dir) => std::path::Path::new(dir).join("lib").join("cmake").join("MPF"),
+        None => std::path::Path::new(sdk.lib.as_ref()?).join("cmake").join("MPF"),
+    };
+    Some(dir.to_string_lossy().replace('\\', "/"))
+}
 
-    // SDK current path
-    let sdk_current = config::current_link();
-    let sdk_current_str = sdk_current.to_string_lossy().replace('\\', "/");
+/// CMAKE_PREFIX_PATH entries: a locally linked SDK first, then Qt, the
+/// installed SDK and linked library components (not plugins, not host)
+fn cmake_prefix_parts(dev_config: &DevConfig, qt_path_fwd: &str) -> Vec<String> {
+    let sdk_current_str = config::current_link().to_string_lossy().replace('\\', "/");
 
     // Build CMAKE_PREFIX_PATH — if SDK is linked locally, prepend it
     let mut prefix_parts: Vec<String> = Vec::new();
 
-    if let Some(sdk_comp) = dev_config.components.get("sdk") {
-        if sdk_comp.mode == ComponentMode::Source {
-            if let Some(lib_path) = &sdk_comp.lib {
-                let sdk_local = std::path::Path::new(lib_path)
-                    .parent()
-                    .map(|p| p.to_string_lossy().replace('\\', "/"))
-                    .unwrap_or_default();
-                if !sdk_local.is_empty() {
-                    prefix_parts.push(sdk_local);
-                }
+    if let Some(sdk_comp) = dev_config.source_component("sdk") {
+        if let Some(dir) = &sdk_comp.cmake_config {
+            prefix_parts.push(dir.clone());
+        } else if let Some(lib_path) = &sdk_comp.lib {
+            let sdk_local = std::path::Path::new(lib_path)
+                .parent()
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+                .unwrap_or_default();
+            if !sdk_local.is_empty() {
+                prefix_parts.push(sdk_local);
             }
         }
     }
 
     prefix_parts.push(qt_path_fwd.to_string());
-    prefix_parts.push(sdk_current_str.clone());
+    prefix_parts.push(sdk_current_str);
 
     // Append linked library component install paths (not plugins, not host)
-    for (name, comp) in &dev_config.components {
+    for (name, comp) in dev_config.enabled_components() {
         if comp.mode != ComponentMode::Source {
             continue;
         }
+        if name == "sdk" {
+            continue;
+        }
+        // An explicit config directory is used as-is, for any component
+        if let Some(dir) = &comp.cmake_config {
+            if !prefix_parts.contains(dir) {
+                prefix_parts.push(dir.clone());
+            }
+            continue;
+        }
         // Skip special components and those with dedicated CMake _DIR variables
-        if name == "sdk" || name == "host" || name.starts_with("plugin-") {
+        if name == "host" || name.starts_with("plugin-") {
             continue;
         }
-        if component_cmake_dir_var(name).is_some() {
+        if component_cmake_dir_var(&name).is_some() {
             continue;
         }
         // Add lib parent as cmake prefix path (the install root)
@@ -75,13 +269,13 @@ fn generate_user_presets(
         }
     }
 
-    let cmake_prefix_path = prefix_parts.join(";");
+    prefix_parts
+}
 
-    // Build QML_IMPORT_PATH parts and package dir variables
+/// QML import paths of linked components, then the SDK's and Qt's
+fn qml_import_parts(dev_config: &DevConfig, qt_path_fwd: &str) -> Vec<String> {
     let mut qml_parts: Vec<String> = Vec::new();
-    let mut extra_cache_vars: Vec<(String, String)> = Vec::new();
-
-    for (name, comp) in &dev_config.components {
+    for (_, comp) in dev_config.enabled_components() {
         if comp.mode != ComponentMode::Source {
             continue;
         }
@@ -91,14 +285,65 @@ fn generate_user_presets(
                 qml_parts.push(qml_fwd);
             }
         }
-        if let Some(var_name) = component_cmake_dir_var(name) {
+    }
+    let sdk_current_str = config::current_link().to_string_lossy().replace('\\', "/");
+    qml_parts.push(format!("{}/qml", sdk_current_str));
+    qml_parts.push(format!("{}/qml", qt_path_fwd));
+    qml_parts
+}
+
+/// Generate CMakeUserPresets.json for a project directory.
+///
+/// Pure logic — no interactive output. Only writes the preset file;
+/// does NOT touch the build directory.
+///
+/// The file is left untouched when its content would not change, unless
+/// `force` is set.
+fn generate_user_presets(
+    project_dir: &std::path::Path,
+    dev_config: &DevConfig,
+    qt_path_fwd: &str,
+    gcc: &str,
+    gpp: &str,
+    opts: &PresetOptions,
+    force: bool,
+) -> Result<PresetOutcome> {
+    // Skip if not a CMake project
+    if infer_build_system(project_dir) != BuildSystem::CMake {
+        return Ok(PresetOutcome::Unsupported);
+    }
+
+    let generator = opts.generator.clone();
+    let multi_config = generator.as_deref().map(is_multi_config).unwrap_or(false);
+    // A toolchain file selects the compilers itself
+    let set_compiler = !opts.no_compiler && opts.toolchain_file.is_none();
+    let launcher = dev_config
+        .compiler_cache
+        .filter(|c| compiler_cache_available(*c));
+
+    let cmake_prefix_path = cmake_prefix_parts(dev_config, qt_path_fwd).join(";");
+    let qml_import_path = qml_import_parts(dev_config, qt_path_fwd).join(";");
+
+    // Package dir variables for components with a dedicated <Pkg>_DIR
+    let mut extra_cache_vars: Vec<(String, String)> = Vec::new();
+    if let Some(dir) = sdk_package_dir(dev_config) {
+        extra_cache_vars.push(("MPF_DIR".to_string(), dir));
+    }
+
+    for (name, comp) in dev_config.enabled_components() {
+        if comp.mode != ComponentMode::Source {
+            continue;
+        }
+        if let Some(var_name) = component_cmake_dir_var(&name) {
             let build_root = comp
-                .lib
-                .as_ref()
-                .and_then(|p| {
-                    std::path::Path::new(p)
-                        .parent()
-                        .map(|pp| pp.to_string_lossy().replace('\\', "/"))
+                .cmake_config
+                .clone()
+                .or_else(|| {
+                    comp.lib.as_ref().and_then(|p| {
+                        std::path::Path::new(p)
+                            .parent()
+                            .map(|pp| pp.to_string_lossy().replace('\\', "/"))
+                    })
                 })
                 .or_else(|| {
                     comp.headers.as_ref().and_then(|p| {
@@ -113,24 +358,35 @@ fn generate_user_presets(
         }
     }
 
-    qml_parts.push(format!("{}/qml", sdk_current_str));
-    qml_parts.push(format!("{}/qml", qt_path_fwd));
-    let qml_import_path = qml_parts.join(";");
-
     // Build JSON
     let mut dev_cache = serde_json::Map::new();
-    dev_cache.insert(
-        "CMAKE_BUILD_TYPE".into(),
-        serde_json::Value::String("Debug".into()),
-    );
-    dev_cache.insert(
-        "CMAKE_C_COMPILER".into(),
-        serde_json::Value::String(gcc.to_string()),
-    );
-    dev_cache.insert(
-        "CMAKE_CXX_COMPILER".into(),
-        serde_json::Value::String(gpp.to_string()),
-    );
+    if !multi_config {
+        dev_cache.insert(
+            "CMAKE_BUILD_TYPE".into(),
+            serde_json::Value::String("Debug".into()),
+        );
+    }
+    if set_compiler {
+        dev_cache.insert(
+            "CMAKE_C_COMPILER".into(),
+            serde_json::Value::String(gcc.to_string()),
+        );
+        dev_cache.insert(
+            "CMAKE_CXX_COMPILER".into(),
+            serde_json::Value::String(gpp.to_string()),
+        );
+    }
+    if let Some(cache) = launcher {
+        for var in ["CMAKE_C_COMPILER_LAUNCHER", "CMAKE_CXX_COMPILER_LAUNCHER"] {
+            dev_cache.insert(var.into(), serde_json::Value::String(cache.program().into()));
+        }
+    }
+    if let Some(toolchain) = &opts.toolchain_file {
+        dev_cache.insert(
+            "CMAKE_TOOLCHAIN_FILE".into(),
+            serde_json::Value::String(toolchain.clone()),
+        );
+    }
     dev_cache.insert(
         "CMAKE_PREFIX_PATH".into(),
         serde_json::Value::String(cmake_prefix_path.clone()),
@@ -151,18 +407,33 @@ fn generate_user_presets(
     }
 
     let mut release_cache = serde_json::Map::new();
-    release_cache.insert(
-        "CMAKE_BUILD_TYPE".into(),
-        serde_json::Value::String("Release".into()),
-    );
-    release_cache.insert(
-        "CMAKE_C_COMPILER".into(),
-        serde_json::Value::String(gcc.to_string()),
-    );
-    release_cache.insert(
-        "CMAKE_CXX_COMPILER".into(),
-        serde_json::Value::String(gpp.to_string()),
-    );
+    if !multi_config {
+        release_cache.insert(
+            "CMAKE_BUILD_TYPE".into(),
+            serde_json::Value::String("Release".into()),
+        );
+    }
+    if set_compiler {
+        release_cache.insert(
+            "CMAKE_C_COMPILER".into(),
+            serde_json::Value::String(gcc.to_string()),
+        );
+        release_cache.insert(
+            "CMAKE_CXX_COMPILER".into(),
+            serde_json::Value::String(gpp.to_string()),
+        );
+    }
+    if let Some(cache) = launcher {
+        for var in ["CMAKE_C_COMPILER_LAUNCHER", "CMAKE_CXX_COMPILER_LAUNCHER"] {
+            release_cache.insert(var.into(), serde_json::Value::String(cache.program().into()));
+        }
+    }
+    if let Some(toolchain) = &opts.toolchain_file {
+        release_cache.insert(
+            "CMAKE_TOOLCHAIN_FILE".into(),
+            serde_json::Value::String(toolchain.clone()),
+        );
+    }
     release_cache.insert(
         "CMAKE_PREFIX_PATH".into(),
         serde_json::Value::String(cmake_prefix_path),
@@ -182,51 +453,541 @@ fn generate_user_presets(
         );
     }
 
-    let presets = serde_json::json!({
-        "version": 6,
-        "configurePresets": [
-            {
-                "name": "dev",
-                "inherits": "base",
-                "displayName": "MPF Dev",
-                "cacheVariables": serde_json::Value::Object(dev_cache)
-            },
-            {
-                "name": "release",
-                "inherits": "base",
-                "displayName": "MPF Release",
-                "binaryDir": "${sourceDir}/build-release",
-                "cacheVariables": serde_json::Value::Object(release_cache)
+    // User variables fill in around the computed ones, never replace them
+    for (key, value) in &dev_config.extra_cmake_vars {
+        for cache in [&mut dev_cache, &mut release_cache] {
+            cache
+                .entry(key.clone())
+                .or_insert_with(|| serde_json::Value::String(value.clone()));
+        }
+    }
+
+    // A project on the Windows filesystem is configured by Windows tools
+    if is_wsl2() && project_dir.starts_with("/mnt") {
+        for cache in [&mut dev_cache, &mut release_cache] {
+            windows_cache_paths(cache);
+        }
+    }
+
+    let mut dev_preset = serde_json::json!({
+        "name": "dev",
+        "displayName": "MPF Dev",
+        "cacheVariables": serde_json::Value::Object(dev_cache)
+    });
+    let mut release_preset = serde_json::json!({
+        "name": "release",
+        "displayName": "MPF Release",
+        "binaryDir": "${sourceDir}/build-release",
+        "cacheVariables": serde_json::Value::Object(release_cache)
+    });
+    if let Some(base) = detect_base_preset(project_dir) {
+        dev_preset["inherits"] = serde_json::Value::String(base.clone());
+        release_preset["inherits"] = serde_json::Value::String(base);
+    }
+    // Pass the sccache cloud bucket through to the configure environment.
+    // Always emitted so the presets don't depend on the caller's shell.
+    if launcher == Some(CompilerCache::Sccache) {
+        let env = serde_json::json!({ "SCCACHE_BUCKET": "$penv{SCCACHE_BUCKET}" });
+        dev_preset["environment"] = env.clone();
+        release_preset["environment"] = env;
+    }
+    if let Some(gen) = &generator {
+        dev_preset["generator"] = serde_json::Value::String(gen.clone());
+        release_preset["generator"] = serde_json::Value::String(gen.clone());
+    }
+
+    let mut dev_build = serde_json::json!({"name": "dev", "configurePreset": "dev"});
+    let mut release_build = serde_json::json!({"name": "release", "configurePreset": "release"});
+    if multi_config {
+        // One build tree holds every configuration; pick it at build time
+        dev_preset["binaryDir"] = "${sourceDir}/build".into();
+        release_preset["binaryDir"] = "${sourceDir}/build".into();
+        dev_build["configuration"] = "Debug".into();
+        release_build["configuration"] = "Release".into();
+    }
+
+    if let Some(system) = opts.condition_platform.as_deref().and_then(host_system_name) {
+        dev_preset["condition"] = platform_condition(system);
+        release_preset["condition"] = platform_condition(system);
+    }
+
+    let mut configure_presets = vec![dev_preset, release_preset];
+    let mut build_presets = vec![dev_build, release_build];
+    if opts.multi_platform {
+        let platform = current_platform();
+        let system = host_system_name(platform).unwrap_or_default();
+        for preset in configure_presets.iter_mut().chain(build_presets.iter_mut()) {
+            for key in ["name", "configurePreset"] {
+                if let Some(name) = preset[key].as_str() {
+                    preset[key] = format!("{}-{}", name, platform).into();
+                }
             }
-        ],
-        "buildPresets": [
-            {"name": "dev", "configurePreset": "dev"},
-            {"name": "release", "configurePreset": "release"}
-        ]
+            if let Some(display) = preset["displayName"].as_str() {
+                preset["displayName"] = format!("{} ({})", display, system).into();
+                preset["condition"] = platform_condition(system);
+            }
+        }
+        // Presets for other platforms come from the developers on those machines
+        let (other_configure, other_build) = other_platform_presets(project_dir, platform);
+        configure_presets.extend(other_configure);
+        build_presets.extend(other_build);
+    }
+
+    let mut presets = serde_json::json!({
+        "version": 6,
+        "configurePresets": configure_presets,
+        "buildPresets": build_presets
     });
+    // JSON has no comments; CMake keeps vendor data for tools like IDEs
+    let build_commands: serde_json::Map<String, serde_json::Value> = dev_config
+        .enabled_components()
+        .into_iter()
+        .filter_map(|(name, comp)| {
+            comp.build_command
+                .map(|cmd| (name, serde_json::Value::String(cmd)))
+        })
+        .collect();
+    if !build_commands.is_empty() {
+        presets["vendor"] = serde_json::json!({ "mpf-dev": { "buildCommands": build_commands } });
+    }
 
-    let output_path = project_dir.join("CMakeUserPresets.json");
     let content = serde_json::to_string_pretty(&presets)?;
-    fs::write(&output_path, &content)
-        .with_context(|| format!("写入 {} 失败", output_path.display()))?;
+    write_if_changed(&project_dir.join("CMakeUserPresets.json"), &content, force)
+}
 
-    Ok(true)
+/// Rewrite the absolute WSL paths (including `;`-separated lists) in preset
+/// cache variables to Windows form, with forward slashes like other presets
+fn windows_cache_paths(cache: &mut serde_json::Map<String, serde_json::Value>) {
+    for value in cache.values_mut() {
+        let Some(text) = value.as_str() else {
+            continue;
+        };
+        if !text.starts_with('/') {
+            continue;
+        }
+        let parts: Vec<String> = text
+            .split(';')
+            .map(|part| {
+                wsl2_to_windows_path(part)
+                    .map(|p| p.replace('\\', "/"))
+                    .unwrap_or_else(|| part.to_string())
+            })
+            .collect();
+        *value = parts.join(";").into();
+    }
+}
+
+/// Configure and build presets for platforms other than `platform` in an
+/// existing multi-platform CMakeUserPresets.json
+fn other_platform_presets(
+    project_dir: &std::path::Path,
+    platform: &str,
+) -> (Vec<serde_json::Value>, Vec<serde_json::Value>) {
+    let existing: serde_json::Value =
+        fs::read_to_string(project_dir.join("CMakeUserPresets.json"))
+            .ok()
+            .and_then(|c| serde_json::from_str(&c).ok())
+            .unwrap_or_default();
+    let other = |preset: &&serde_json::Value| {
+        let name = preset["name"].as_str().unwrap_or_default();
+        PRESET_PLATFORMS
+            .iter()
+            .filter(|(p, _)| *p != platform)
+            .any(|(p, _)| name == format!("dev-{}", p) || name == format!("release-{}", p))
+    };
+    let pick = |key: &str| -> Vec<serde_json::Value> {
+        existing[key]
+            .as_array()
+            .map(|list| list.iter().filter(other).cloned().collect())
+            .unwrap_or_default()
+    };
+    (pick("configurePresets"), pick("buildPresets"))
+}
+
+/// Write a generated file unless it already has this content (or `force`)
+fn write_if_changed(path: &std::path::Path, content: &str, force: bool) -> Result<PresetOutcome> {
+    if !force && fs::read_to_string(path).ok().as_deref() == Some(content) {
+        return Ok(PresetOutcome::Unchanged);
+    }
+    fs::write(path, content).with_context(|| format!("写入 {} 失败", path.display()))?;
+    Ok(PresetOutcome::Written)
+}
+
+/// Header and library directories of linked components, then the SDK's
+fn include_and_lib_dirs(dev_config: &DevConfig) -> (Vec<String>, Vec<String>) {
+    let mut includes: Vec<String> = Vec::new();
+    let mut libs: Vec<String> = Vec::new();
+    for (_, comp) in dev_config.enabled_components() {
+        if comp.mode != ComponentMode::Source {
+            continue;
+        }
+        for (dir, list) in [(&comp.headers, &mut includes), (&comp.lib, &mut libs)] {
+            if let Some(dir) = dir {
+                let fwd = dir.replace('\\', "/");
+                if !list.contains(&fwd) {
+                    list.push(fwd);
+                }
+            }
+        }
+    }
+    let sdk_current_str = config::current_link().to_string_lossy().replace('\\', "/");
+    includes.push(format!("{}/include", sdk_current_str));
+    libs.push(format!("{}/lib", sdk_current_str));
+    (includes, libs)
+}
+
+/// Generate mpf-dev.pri for a QMake project.
+///
+/// qmake has no equivalent of user presets, so the file has to be pulled in
+/// with `include(mpf-dev.pri)` from the project's .pro file.
+fn generate_qmake_pri(
+    project_dir: &std::path::Path,
+    dev_config: &DevConfig,
+    qt_path_fwd: &str,
+    force: bool,
+) -> Result<PresetOutcome> {
+    let (includes, libs) = include_and_lib_dirs(dev_config);
+    let quoted = |items: Vec<String>| {
+        items
+            .iter()
+            .map(|item| format!(" \\\n    \"{}\"", item))
+            .collect::<String>()
+    };
+
+    let mut content = String::from(
+        "# Generated by mpf-dev; rewritten on link/unlink, do not edit.\n\
+         # Add `include(mpf-dev.pri)` to your .pro file.\n\n",
+    );
+    content.push_str(&format!(
+        "MPF_SDK_ROOT = \"{}\"\n\n",
+        config::current_link().to_string_lossy().replace('\\', "/")
+    ));
+    content.push_str(&format!("INCLUDEPATH +={}\n\n", quoted(includes)));
+    content.push_str(&format!(
+        "LIBS +={}\n\n",
+        quoted(libs.into_iter().map(|l| format!("-L{}", l)).collect())
+    ));
+    content.push_str(&format!(
+        "QML_IMPORT_PATH +={}\n",
+        quoted(qml_import_parts(dev_config, qt_path_fwd))
+    ));
+
+    write_if_changed(&project_dir.join("mpf-dev.pri"), &content, force)
+}
+
+/// Generate meson.native for a Meson project.
+///
+/// Used with `meson setup build --native-file meson.native`; lists the
+/// compilers, Qt's qmake and the CMake/pkg-config search paths.
+fn generate_meson_native(
+    project_dir: &std::path::Path,
+    dev_config: &DevConfig,
+    qt_path_fwd: &str,
+    gcc: &str,
+    gpp: &str,
+    force: bool,
+) -> Result<PresetOutcome> {
+    let quote = |s: &str| format!("'{}'", s.replace('\\', "/").replace('\'', "\\'"));
+    let array = |items: &[String]| {
+        let quoted: Vec<String> = items.iter().map(|s| quote(s)).collect();
+        format!("[{}]", quoted.join(", "))
+    };
+
+    let qmake_name = if cfg!(windows) { "qmake.exe" } else { "qmake" };
+    let (_, libs) = include_and_lib_dirs(dev_config);
+    let mut pkg_config: Vec<String> = vec![format!("{}/lib/pkgconfig", qt_path_fwd)];
+    pkg_config.extend(libs.iter().map(|l| format!("{}/pkgconfig", l)));
+
+    let content = format!(
+        "# Generated by mpf-dev; rewritten on link/unlink, do not edit.\n\
+         # meson setup build --native-file meson.native\n\n\
+         [binaries]\n\
+         c = {c}\n\
+         cpp = {cpp}\n\
+         qmake = {qmake}\n\n\
+         [built-in options]\n\
+         cmake_prefix_path = {prefix}\n\
+         pkg_config_path = {pkg_config}\n",
+        c = quote(gcc),
+        cpp = quote(gpp),
+        qmake = quote(&format!("{}/bin/{}", qt_path_fwd, qmake_name)),
+        prefix = array(&cmake_prefix_parts(dev_config, qt_path_fwd)),
+        pkg_config = array(&pkg_config),
+    );
+
+    write_if_changed(&project_dir.join("meson.native"), &content, force)
+}
+
+/// Generate the dev build config matching the project's build system
+fn generate_build_config(
+    project_dir: &std::path::Path,
+    dev_config: &DevConfig,
+    qt_path_fwd: &str,
+    gcc: &str,
+    gpp: &str,
+    force: bool,
+) -> Result<PresetOutcome> {
+    match infer_build_system(project_dir) {
+        BuildSystem::CMake => {
+            let opts = PresetOptions::from_existing(project_dir);
+            generate_user_presets(project_dir, dev_config, qt_path_fwd, gcc, gpp, &opts, force)
+        }
+        BuildSystem::QMake => generate_qmake_pri(project_dir, dev_config, qt_path_fwd, force),
+        BuildSystem::Meson => {
+            generate_meson_native(project_dir, dev_config, qt_path_fwd, gcc, gpp, force)
+        }
+        BuildSystem::Unknown => Ok(PresetOutcome::Unsupported),
+    }
+}
+
+/// Content of a `.clangd` file pointing clangd at the CMake compile database.
+///
+/// `--query-driver` is a clangd command-line option rather than a config
+/// key, so it is written as a comment for the user to copy into their
+/// editor's clangd arguments.
+fn generate_clangd_config(build_dir: &str, query_driver: &str) -> String {
+    format!(
+        "# Generated by mpf-dev init --clangd\n\
+         # Start clangd with the following argument so it can query the MinGW\n\
+         # toolchain for its system include paths:\n\
+         #   --query-driver={query_driver}\n\
+         CompileFlags:\n\
+         \x20 CompilationDatabase: {build_dir}\n\
+         InlayHints:\n\
+         \x20 Enabled: true\n"
+    )
+}
+
+/// Content of a `.clang-tidy` file with general-purpose checks.
+///
+/// Checks that fire constantly on Qt's macro-heavy code (magic numbers in
+/// layouts, `public slots:` access specifiers, ...) are turned off.
+/// Diagnostics are limited to headers matching `header_filter`.
+fn generate_clang_tidy_config(header_filter: &str) -> String {
+    format!(
+        "# Generated by mpf-dev init --clang-tidy\n\
+         Checks: >\n\
+         \x20 -*,\n\
+         \x20 bugprone-*,\n\
+         \x20 modernize-*,\n\
+         \x20 performance-*,\n\
+         \x20 readability-*,\n\
+         \x20 -readability-magic-numbers,\n\
+         \x20 -cppcoreguidelines-avoid-magic-numbers,\n\
+         \x20 -readability-redundant-access-specifiers,\n\
+         \x20 -readability-identifier-length,\n\
+         \x20 -modernize-use-trailing-return-type,\n\
+         \x20 -bugprone-easily-swappable-parameters\n\
+         WarningsAsErrors: ''\n\
+         HeaderFilterRegex: '{header_filter}'\n\
+         FormatStyle: file\n"
+    )
+}
+
+/// HeaderFilterRegex for a project: the `headers` path of a component linked
+/// from this project, else its `include/` directory, else the whole project
+fn clang_tidy_header_filter(project_dir: &std::path::Path, dev_config: &DevConfig) -> String {
+    let root = normalize_path(project_dir.to_path_buf()).replace('\\', "/");
+    let headers = dev_config
+        .components
+        .values()
+        .filter(|c| c.all_roots().any(|r| r.replace('\\', "/") == root))
+        .find_map(|c| c.headers.as_ref())
+        .map(|h| h.replace('\\', "/"));
+    let dir = headers.unwrap_or_else(|| {
+        if project_dir.join("include").is_dir() {
+            format!("{}/include", root)
+        } else {
+            root.clone()
+        }
+    });
+    let escaped: String = dir
+        .chars()
+        .map(|c| {
+            if ".+*?()[]{}|^$\\".contains(c) {
+                format!("\\{}", c)
+            } else {
+                c.to_string()
+            }
+        })
+        .collect();
+    format!("^{}/.*", escaped)
+}
+
+/// Name of the configuration mpf-dev owns in c_cpp_properties.json
+const VSCODE_CONFIG_NAME: &str = "MPF Dev";
+
+/// Write .vscode/c_cpp_properties.json for the cpptools extension.
+///
+/// Only the "MPF Dev" configuration is replaced; other configurations in an
+/// existing file are kept.
+fn generate_vscode_config(
+    project_dir: &std::path::Path,
+    dev_config: &DevConfig,
+    qt_path_fwd: &str,
+    gpp: &str,
+) -> Result<std::path::PathBuf> {
+    let (mut include_path, _) = include_and_lib_dirs(dev_config);
+    include_path.insert(0, "${workspaceFolder}/**".to_string());
+    include_path.push(format!("{}/include/**", qt_path_fwd));
+
+    let configuration = serde_json::json!({
+        "name": VSCODE_CONFIG_NAME,
+        "includePath": include_path,
+        "compilerPath": gpp,
+        "compileCommands": "${workspaceFolder}/build/compile_commands.json",
+        "cppStandard": "c++17"
+    });
+
+    let dir = project_dir.join(".vscode");
+    fs::create_dir_all(&dir)?;
+    let path = dir.join("c_cpp_properties.json");
+    let mut properties: serde_json::Value = fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_else(|| serde_json::json!({ "version": 4 }));
+    let mut configurations: Vec<serde_json::Value> = properties["configurations"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    configurations.retain(|c| c["name"] != VSCODE_CONFIG_NAME);
+    configurations.insert(0, configuration);
+    properties["configurations"] = serde_json::Value::Array(configurations);
+
+    fs::write(&path, serde_json::to_string_pretty(&properties)?)
+        .with_context(|| format!("写入 {} 失败", path.display()))?;
+    Ok(path)
+}
+
+/// Write .idea/cmake.xml with a CLion CMake profile that uses the dev preset.
+///
+/// An existing cmake.xml holds the user's other profiles, so it is only
+/// replaced with `force`.
+fn generate_clion_config(
+    project_dir: &std::path::Path,
+    force: bool,
+) -> Result<Option<std::path::PathBuf>> {
+    let dir = project_dir.join(".idea");
+    let path = dir.join("cmake.xml");
+    if path.exists() && !force {
+        return Ok(None);
+    }
+    let content = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+                   <project version=\"4\">\n\
+                   \x20 <component name=\"CMakeSharedSettings\">\n\
+                   \x20   <configurations>\n\
+                   \x20     <configuration PROFILE_NAME=\"MPF Dev\" ENABLED=\"true\" \
+                   CONFIG_NAME=\"Debug\" GENERATION_OPTIONS=\"--preset dev\" \
+                   GENERATION_DIR=\"build\" />\n\
+                   \x20   </configurations>\n\
+                   \x20 </component>\n\
+                   </project>\n";
+    fs::create_dir_all(&dir)?;
+    fs::write(&path, content).with_context(|| format!("写入 {} 失败", path.display()))?;
+    Ok(Some(path))
+}
+
+/// Write qtcreator-preset.cmake, an initial-cache script (`cmake -C`) with
+/// the dev preset's cache variables, for Qt Creator kits that don't use
+/// CMakeUserPresets.json.
+fn generate_qtcreator_config(project_dir: &std::path::Path) -> Result<std::path::PathBuf> {
+    let presets_path = project_dir.join("CMakeUserPresets.json");
+    let presets: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(&presets_path)
+            .with_context(|| format!("读取 {} 失败", presets_path.display()))?,
+    )?;
+    let cache_vars = presets["configurePresets"]
+        .as_array()
+        .and_then(|list| {
+            list.iter()
+                .find(|p| p["name"].as_str().is_some_and(is_dev_preset))
+        })
+        .and_then(|p| p["cacheVariables"].as_object())
+        .cloned()
+        .unwrap_or_default();
+
+    let mut content = String::from(
+        "# Generated by mpf-dev init --ide qtcreator\n\
+         # Qt Creator: Projects > Build > Initial Configuration, add\n\
+         #   -C %{sourceDir}/qtcreator-preset.cmake\n",
+    );
+    for (key, value) in &cache_vars {
+        let value = value.as_str().unwrap_or_default();
+        content.push_str(&format!(
+            "set({} \"{}\" CACHE STRING \"\")\n",
+            key,
+            value.replace('\\', "/").replace('"', "\\\"")
+        ));
+    }
+
+    let path = project_dir.join("qtcreator-preset.cmake");
+    fs::write(&path, content).with_context(|| format!("写入 {} 失败", path.display()))?;
+    Ok(path)
+}
+
+/// Append `pattern` to the project's .gitignore unless a line already matches
+fn ensure_gitignored(project_dir: &std::path::Path, pattern: &str) -> Result<()> {
+    let path = project_dir.join(".gitignore");
+    let mut content = fs::read_to_string(&path).unwrap_or_default();
+    if content.lines().any(|l| l.trim().trim_start_matches('/') == pattern) {
+        return Ok(());
+    }
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(pattern);
+    content.push('\n');
+    fs::write(&path, content).with_context(|| format!("写入 {} 失败", path.display()))
+}
+
+/// How long detected Qt/compiler paths stay valid in dev.json
+const TOOLCHAIN_CACHE_SECS: u64 = 24 * 60 * 60;
+
+/// Qt prefix and C/C++ compilers from dev.json, if the cache is still valid.
+///
+/// The cache is ignored once expired, when a cached path no longer exists,
+/// or when QT_DIR / Qt6_DIR now point somewhere else.
+fn cached_toolchain(dev_config: &DevConfig) -> Option<(String, String, String)> {
+    let expiry = dev_config.compiler_cache_expiry?;
+    if (chrono::Utc::now().timestamp() as u64) >= expiry {
+        return None;
+    }
+    let qt = dev_config.cached_qt_path.clone()?;
+    let gcc = dev_config.cached_gcc_path.clone()?;
+    let gpp = dev_config.cached_gpp_path.clone()?;
+    if let Ok(env_qt) = env::var("QT_DIR").or_else(|_| env::var("Qt6_DIR")) {
+        if env_qt != qt {
+            return None;
+        }
+    }
+    let exists = |p: &str| std::path::Path::new(p).exists();
+    (exists(&qt) && exists(&gcc) && exists(&gpp)).then_some((qt, gcc, gpp))
+}
+
+/// Remember freshly detected toolchain paths for TOOLCHAIN_CACHE_SECS
+fn store_toolchain(dev_config: &mut DevConfig, qt: &str, gcc: &str, gpp: &str) {
+    dev_config.cached_qt_path = Some(qt.to_string());
+    dev_config.cached_gcc_path = Some(gcc.to_string());
+    dev_config.cached_gpp_path = Some(gpp.to_string());
+    dev_config.compiler_cache_expiry =
+        Some(chrono::Utc::now().timestamp() as u64 + TOOLCHAIN_CACHE_SECS);
 }
 
 /// Re-init all projects that have a known root directory.
 ///
 /// Called after link/unlink to propagate dev.json changes to all
-/// CMakeUserPresets.json files. Only regenerates preset files — does
+/// CMakeUserPresets.json (or mpf-dev.pri / meson.native) files. Only
+/// regenerates those files — does
 /// NOT touch build directories. Qt Creator detects preset changes
 /// automatically. Silently skips projects whose root no longer exists.
-pub(super) fn reinit_all(dev_config: &DevConfig) -> Result<()> {
+pub(super) fn reinit_all(dev_config: &mut DevConfig) -> Result<()> {
     // Collect roots, deduplicate by normalized path (multiple components can
     // share the same project root, e.g. plugin-lib and plugin-lib-qml).
     let mut seen = std::collections::HashSet::new();
-    let roots: Vec<&str> = dev_config
-        .components
-        .values()
-        .filter_map(|c| c.root.as_deref())
+    let roots: Vec<String> = dev_config
+        .enabled_components()
+        .iter()
+        .flat_map(|(_, c)| c.all_roots().cloned())
         .filter(|r| seen.insert(r.to_lowercase().replace('\\', "/")))
         .collect();
 
@@ -234,16 +995,24 @@ pub(super) fn reinit_all(dev_config: &DevConfig) -> Result<()> {
         return Ok(());
     }
 
-    // Detect Qt/MinGW once for all projects
-    let qt_path = match detect_qt_path() {
-        Some(p) => p,
-        None => return Ok(()), // Can't detect Qt — skip silently
+    // Detect Qt/MinGW once for all projects, or reuse the cached result
+    let (qt_path, gcc, gpp) = match cached_toolchain(dev_config) {
+        Some(toolchain) => toolchain,
+        None => {
+            let qt_path = match detect_qt_path() {
+                Some(p) => p,
+                None => return Ok(()), // Can't detect Qt — skip silently
+            };
+            let (gcc, gpp) = match detect_compilers(&qt_path) {
+                Some(p) => p,
+                None => return Ok(()),
+            };
+            store_toolchain(dev_config, &qt_path, &gcc, &gpp);
+            dev_config.save()?;
+            (qt_path, gcc, gpp)
+        }
     };
     let qt_path_fwd = qt_path.replace('\\', "/");
-    let (gcc, gpp) = match detect_mingw_path(&qt_path) {
-        Some(p) => p,
-        None => return Ok(()),
-    };
 
     println!("{} 正在重新初始化 {} 个项目...", "→".cyan(), roots.len());
 
@@ -259,13 +1028,16 @@ pub(super) fn reinit_all(dev_config: &DevConfig) -> Result<()> {
             println!("  {} {} （目录不存在）", "⚠".yellow(), name);
             continue;
         }
-        match generate_user_presets(path, dev_config, &qt_path_fwd, &gcc, &gpp) {
-            Ok(true) => {
+        match generate_build_config(path, dev_config, &qt_path_fwd, &gcc, &gpp, false) {
+            Ok(PresetOutcome::Written) => {
                 println!("  {} {}", "✓".green(), name);
                 updated += 1;
             }
-            Ok(false) => {
-                println!("  {} {} （无 CMakeLists.txt）", "⚠".yellow(), name);
+            Ok(PresetOutcome::Unchanged) => {
+                println!("  {} {} （无变化）", "=".dimmed(), name);
+            }
+            Ok(PresetOutcome::Unsupported) => {
+                println!("  {} {} （未识别的构建系统）", "⚠".yellow(), name);
             }
             Err(e) => {
                 println!("  {} {} — {}", "✗".red(), name, e);
@@ -274,8 +1046,10 @@ pub(super) fn reinit_all(dev_config: &DevConfig) -> Result<()> {
     }
 
     if updated > 0 {
+        dev_config.last_reinit = Some(chrono::Utc::now().to_rfc3339());
+        dev_config.save()?;
         println!(
-            "\n{} {} 个项目的 CMakeUserPresets.json 已更新。",
+            "\n{} {} 个项目的开发配置已更新。",
             "✓".green(),
             updated,
         );
@@ -288,33 +1062,103 @@ pub(super) fn reinit_all(dev_config: &DevConfig) -> Result<()> {
     Ok(())
 }
 
-/// Init command: generate CMakeUserPresets.json for the current project.
+/// Register this project's root in dev.json so reinit_all can find it.
+fn register_project_root(dev_config: &mut DevConfig, cwd: &std::path::Path) {
+    let cwd_normalized = normalize_path(cwd.to_path_buf());
+    let cwd_build_prefix = format!("{}/build", cwd_normalized.replace('\\', "/"));
+    for (_name, comp) in dev_config.components.iter_mut() {
+        if comp.root.is_some() {
+            continue;
+        }
+        let paths = [&comp.lib, &comp.qml, &comp.plugin, &comp.headers, &comp.bin];
+        let matches = paths.iter().any(|p| {
+            p.as_ref()
+                .map(|s| s.replace('\\', "/").starts_with(&cwd_build_prefix))
+                .unwrap_or(false)
+        });
+        if matches {
+            comp.root = Some(cwd_normalized.clone());
+        }
+    }
+}
+
+/// Init command: generate CMakeUserPresets.json for the current project
+/// (mpf-dev.pri for QMake, meson.native for Meson).
 ///
 /// With `--clean`: deletes the entire `build/` directory.
 /// Without `--clean`: deletes only `build/CMakeCache.txt` and `build/CMakeFiles/`
 /// so that the new preset takes effect cleanly.
-pub fn init(clean: bool) -> Result<()> {
+pub fn init(opts: InitOptions) -> Result<()> {
+    let InitOptions {
+        clean,
+        generator,
+        clangd,
+        clang_tidy,
+        ide,
+        no_compiler,
+        toolchain_file,
+        force,
+        refresh_cache,
+        compiler_cache,
+        condition_platform,
+        multi_platform,
+    } = opts;
+
     println!("{}", "MPF 项目初始化".bold().cyan());
 
     let cwd = env::current_dir()?;
 
-    // Verify CMakeLists.txt exists
-    if !cwd.join("CMakeLists.txt").exists() {
-        bail!("当前目录未找到 CMakeLists.txt，请在 CMake 项目根目录执行此命令。");
+    let build_system = infer_build_system(&cwd);
+    if build_system == BuildSystem::Unknown {
+        bail!("当前目录未找到 CMakeLists.txt、*.pro 或 meson.build，请在项目根目录执行此命令。");
     }
 
     // Load dev.json
     let mut dev_config = DevConfig::load().unwrap_or_default();
 
-    // Detect Qt path
-    let qt_path = detect_qt_path().context(
-        "未检测到 Qt 安装路径，请设置 QT_DIR 或 Qt6_DIR 环境变量。",
-    )?;
+    let cached = if refresh_cache {
+        None
+    } else {
+        cached_toolchain(&dev_config)
+    };
+    let (qt_path, gcc, gpp) = match cached {
+        Some(toolchain) => toolchain,
+        None => {
+            // Detect Qt path
+            let qt_path = detect_qt_path().context(
+                "未检测到 Qt 安装路径，请设置 QT_DIR 或 Qt6_DIR 环境变量。",
+            )?;
+            // Detect MinGW compilers (falls back to compilers on PATH)
+            let (gcc, gpp) = detect_compilers(&qt_path)
+                .context("未在 Qt Tools 目录或 PATH 中检测到 C/C++ 编译器。")?;
+            store_toolchain(&mut dev_config, &qt_path, &gcc, &gpp);
+            (qt_path, gcc, gpp)
+        }
+    };
     let qt_path_fwd = qt_path.replace('\\', "/");
 
-    // Detect MinGW compilers
-    let (gcc, gpp) = detect_mingw_path(&qt_path)
-        .context("未在 Qt Tools 目录下检测到 MinGW 编译器。")?;
+    // QMake/Meson projects only get their include file; the CMake options don't apply
+    if build_system != BuildSystem::CMake {
+        let outcome = generate_build_config(&cwd, &dev_config, &qt_path_fwd, &gcc, &gpp, force)?;
+        register_project_root(&mut dev_config, &cwd);
+        dev_config.save()?;
+
+        let (file, usage) = if build_system == BuildSystem::QMake {
+            ("mpf-dev.pri", "在 .pro 文件中添加 include(mpf-dev.pri)")
+        } else {
+            ("meson.native", "meson setup build --native-file meson.native")
+        };
+        let output_path = cwd.join(file);
+        if outcome == PresetOutcome::Written {
+            println!("{} 已生成 {}", "✓".green(), output_path.display());
+        } else {
+            println!("{} {} 无变化，已跳过", "=".dimmed(), output_path.display());
+        }
+        println!();
+        println!("使用方式：");
+        println!("  {}", usage);
+        return Ok(());
+    }
 
     // Check if CMakePresets.json exists; if not, generate a base one
     let base_presets_path = cwd.join("CMakePresets.json");
@@ -325,7 +1169,7 @@ pub fn init(clean: bool) -> Result<()> {
                 {
                     "name": "base",
                     "hidden": true,
-                    "generator": "Ninja",
+                    "generator": generator.as_deref().unwrap_or("Ninja"),
                     "binaryDir": "${sourceDir}/build",
                     "cacheVariables": {
                         "CMAKE_EXPORT_COMPILE_COMMANDS": "ON"
@@ -344,6 +1188,46 @@ pub fn init(clean: bool) -> Result<()> {
         println!("  {} 请将此文件提交到代码仓库", "→".cyan());
     }
 
+    if let Some(cache) = compiler_cache {
+        dev_config.compiler_cache = Some(cache);
+        if !compiler_cache_available(cache) {
+            println!(
+                "{} 未在 PATH 中找到 {}，preset 中暂不启用（安装后重新执行 init 即可生效）",
+                "Warning:".yellow(),
+                cache.program()
+            );
+        }
+    }
+
+    // Generate CMakeUserPresets.json
+    let toolchain_file = match toolchain_file {
+        Some(path) => {
+            let p = std::path::PathBuf::from(&path);
+            if !p.is_file() {
+                bail!("工具链文件不存在：{}", path);
+            }
+            Some(normalize_path(cwd.join(p)).replace('\\', "/"))
+        }
+        None => None,
+    };
+    let existing = PresetOptions::from_existing(&cwd);
+    let preset_opts = PresetOptions {
+        generator: generator.clone().or(existing.generator),
+        no_compiler,
+        toolchain_file,
+        condition_platform: condition_platform.or(existing.condition_platform),
+        multi_platform,
+    };
+    let outcome = generate_user_presets(
+        &cwd,
+        &dev_config,
+        &qt_path_fwd,
+        &gcc,
+        &gpp,
+        &preset_opts,
+        force,
+    )?;
+
     // Clean build artifacts based on --clean flag
     let build_dir = cwd.join("build");
     if clean {
@@ -351,8 +1235,9 @@ pub fn init(clean: bool) -> Result<()> {
         if build_dir.exists() {
             let _ = fs::remove_dir_all(&build_dir);
         }
-    } else {
-        // Default: only remove CMake cache so new preset takes effect
+    } else if outcome == PresetOutcome::Written {
+        // Default: only remove CMake cache so new preset takes effect.
+        // Unchanged presets keep the cache to avoid a full re-configure.
         let _ = fs::remove_file(build_dir.join("CMakeCache.txt"));
         let cmake_files = build_dir.join("CMakeFiles");
         if cmake_files.exists() {
@@ -360,41 +1245,88 @@ pub fn init(clean: bool) -> Result<()> {
         }
     }
 
-    // Generate CMakeUserPresets.json
-    generate_user_presets(&cwd, &dev_config, &qt_path_fwd, &gcc, &gpp)?;
+    register_project_root(&mut dev_config, &cwd);
+    dev_config.save()?;
 
-    // Register this project's root in dev.json so reinit_all can find it.
-    let cwd_normalized = normalize_path(cwd.clone());
-    let cwd_build_prefix = format!("{}/build", cwd_normalized.replace('\\', "/"));
-    for (_name, comp) in dev_config.components.iter_mut() {
-        if comp.root.is_some() {
-            continue;
+    let output_path = cwd.join("CMakeUserPresets.json");
+    if outcome == PresetOutcome::Written {
+        println!("{} 已生成 {}", "✓".green(), output_path.display());
+    } else {
+        println!(
+            "{} {} 无变化，已跳过（使用 --force 强制重新生成）",
+            "=".dimmed(),
+            output_path.display()
+        );
+    }
+
+    if clangd {
+        let driver_dir = std::path::Path::new(&gpp)
+            .parent()
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_default();
+        let query_driver = format!("{}/*", driver_dir);
+        let clangd_path = cwd.join(".clangd");
+        fs::write(&clangd_path, generate_clangd_config("build", &query_driver))
+            .with_context(|| format!("写入 {} 失败", clangd_path.display()))?;
+        println!("{} 已生成 {}", "✓".green(), clangd_path.display());
+        println!(
+            "  {} 请在编辑器的 clangd 参数中添加 --query-driver={}",
+            "→".cyan(),
+            query_driver
+        );
+    }
+    match ide.as_deref() {
+        Some("vscode") => {
+            let path = generate_vscode_config(&cwd, &dev_config, &qt_path_fwd, &gpp)?;
+            println!("{} 已生成 {}", "✓".green(), path.display());
         }
-        let paths = [&comp.lib, &comp.qml, &comp.plugin, &comp.headers, &comp.bin];
-        let matches = paths.iter().any(|p| {
-            p.as_ref()
-                .map(|s| s.replace('\\', "/").starts_with(&cwd_build_prefix))
-                .unwrap_or(false)
-        });
-        if matches {
-            comp.root = Some(cwd_normalized.clone());
+        Some("clion") => match generate_clion_config(&cwd, force)? {
+            Some(path) => println!("{} 已生成 {}", "✓".green(), path.display()),
+            None => println!(
+                "{} .idea/cmake.xml 已存在，已跳过（使用 --force 覆盖）",
+                "=".dimmed()
+            ),
+        },
+        Some("qtcreator") => {
+            let path = generate_qtcreator_config(&cwd)?;
+            println!("{} 已生成 {}", "✓".green(), path.display());
+            println!(
+                "  {} 在 Qt Creator 的初始 CMake 参数中添加 -C %{{sourceDir}}/qtcreator-preset.cmake",
+                "→".cyan()
+            );
         }
+        _ => {}
+    }
+    if clang_tidy {
+        let tidy_path = cwd.join(".clang-tidy");
+        let header_filter = clang_tidy_header_filter(&cwd, &dev_config);
+        fs::write(&tidy_path, generate_clang_tidy_config(&header_filter))
+            .with_context(|| format!("写入 {} 失败", tidy_path.display()))?;
+        // Check selection is a personal preference, so keep it out of the repo
+        ensure_gitignored(&cwd, ".clang-tidy")?;
+        println!("{} 已生成 {}（已加入 .gitignore）", "✓".green(), tidy_path.display());
     }
-    dev_config.save()?;
-
-    let output_path = cwd.join("CMakeUserPresets.json");
-    println!("{} 已生成 {}", "✓".green(), output_path.display());
     if clean {
         println!("{} 构建目录已清空（--clean）", "✓".green());
-    } else {
+    } else if outcome == PresetOutcome::Written {
         println!("{} CMake 缓存已清除", "✓".green());
     }
     println!();
-    println!("  预设：{}, {}", "dev".green(), "release".green());
+    let suffix = if preset_opts.multi_platform {
+        format!("-{}", current_platform())
+    } else {
+        String::new()
+    };
+    let dev_name = format!("dev{}", suffix);
+    println!(
+        "  预设：{}, {}",
+        dev_name.green(),
+        format!("release{}", suffix).green()
+    );
     println!();
     println!("使用方式：");
     println!("  # 根据预设生成构建系统（配置 CMake）");
-    println!("  cmake --preset dev");
+    println!("  cmake --preset {}", dev_name);
     println!("  # 编译项目");
     println!("  cmake --build build");
 
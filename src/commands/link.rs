@@ -1,23 +1,30 @@
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use colored::*;
+use serde::Deserialize;
 use std::env;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use crate::config::{
-    self, ComponentConfig, ComponentMode, DevConfig, KNOWN_COMPONENTS,
+    self, parse_semver, ComponentConfig, ComponentMode, DevConfig, KNOWN_COMPONENTS,
 };
-use crate::LinkAction;
+use crate::{LinkAction, LinkFlags};
 
-use super::{normalize_path, infer_project_root};
+use super::{confirm, normalize_path, infer_project_root, shell_command};
 use super::init::reinit_all;
 
 /// New link action handler - dispatches to appropriate link function
-pub fn link_action(action: LinkAction) -> Result<()> {
+pub async fn link_action(action: LinkAction) -> Result<()> {
     match action {
-        LinkAction::Plugin { name, path } => link_plugin(&name, &path),
-        LinkAction::Host { path } => link_host(&path),
-        LinkAction::Component { name, path } => link_component(&name, &path),
-        LinkAction::Sdk { path } => link_sdk(&path),
+        LinkAction::Plugin { name, path, flags } => link_plugin(&name, &path, &flags),
+        LinkAction::Host { path, flags } => link_host(&path, &flags).await,
+        LinkAction::Component { name, path, flags } => link_component(&name, &path, &flags),
+        LinkAction::Sdk {
+            path,
+            ignore_version,
+            flags,
+        } => link_sdk(&path, ignore_version, &flags),
         LinkAction::Manual {
             name,
             lib,
@@ -25,10 +32,237 @@ pub fn link_action(action: LinkAction) -> Result<()> {
             plugin,
             headers,
             bin,
-        } => link(&name, lib, qml, plugin, headers, bin, None),
+            flags,
+        } => link(&name, lib, qml, plugin, headers, bin, None, &flags),
     }
 }
 
+/// Run a hook command through the platform shell, failing on non-zero exit
+fn run_hook(hook: &str, component: &str) -> Result<()> {
+    println!("{} Running hook: {}", "->".cyan(), hook.dimmed());
+
+    let status = shell_command(hook)
+        .env("MPF_LINK_COMPONENT", component)
+        .status()
+        .with_context(|| format!("Failed to run hook '{}'", hook))?;
+
+    if !status.success() {
+        bail!("Hook '{}' failed ({})", hook, status);
+    }
+    Ok(())
+}
+
+/// Whether `dir` or any of its subdirectories contains a `qmldir` file
+fn has_qmldir(dir: &Path) -> bool {
+    if dir.join("qmldir").is_file() {
+        return true;
+    }
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.path().is_dir())
+                .any(|e| has_qmldir(&e.path()))
+        })
+        .unwrap_or(false)
+}
+
+/// Check that a QML path holds at least one QML module (a `qmldir` file).
+/// Only warns by default, since the build may not have produced it yet.
+fn check_qml_path(qml_path: &str, strict: bool) -> Result<()> {
+    let dir = Path::new(qml_path);
+    if has_qmldir(dir) {
+        return Ok(());
+    }
+    let msg = format!(
+        "No qmldir found under {} (expected e.g. {})",
+        dir.display(),
+        dir.join("<Module>").join("qmldir").display()
+    );
+    if strict {
+        bail!("{}", msg);
+    }
+    println!("{} {}", "Warning:".yellow(), msg);
+    Ok(())
+}
+
+/// Shared libraries (`*.dll` / `*.so` / `*.dylib` for the platform) directly in `dir`
+fn find_shared_libs(dir: &Path) -> Vec<PathBuf> {
+    let ext = if cfg!(windows) {
+        "dll"
+    } else if cfg!(target_os = "macos") {
+        "dylib"
+    } else {
+        "so"
+    };
+    let mut libs: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.is_file() && p.extension().is_some_and(|e| e == ext))
+                .collect()
+        })
+        .unwrap_or_default();
+    libs.sort();
+    libs
+}
+
+/// Check that a plugin's library directory holds built shared libraries and
+/// list them. Only warns by default, like `check_qml_path`.
+fn check_plugin_libs(lib_path: &str, strict: bool) -> Result<()> {
+    let dir = Path::new(lib_path);
+    let libs = find_shared_libs(dir);
+    if libs.is_empty() {
+        let msg = format!(
+            "No shared libraries found in {} (build the plugin first)",
+            dir.display()
+        );
+        if strict {
+            bail!("{}", msg);
+        }
+        println!("{} {}", "Warning:".yellow(), msg);
+        return Ok(());
+    }
+    for lib in &libs {
+        if let Some(file_name) = lib.file_name() {
+            println!("    {}", file_name.to_string_lossy());
+        }
+    }
+    Ok(())
+}
+
+/// Carry user-managed settings (hooks) over from an existing entry
+/// so re-linking a component only replaces its paths.
+fn keep_user_settings(existing: &ComponentConfig, comp: &mut ComponentConfig) {
+    comp.pre_link_hook = existing.pre_link_hook.clone();
+    comp.post_link_hook = existing.post_link_hook.clone();
+    comp.locked = existing.locked;
+    comp.enabled = existing.enabled;
+    comp.priority = existing.priority;
+    comp.build_command = existing.build_command.clone();
+    comp.cmake_config = existing.cmake_config.clone();
+    comp.roots = existing.roots.clone();
+}
+
+/// Field name/value pairs of a component config, in display order
+fn component_fields(comp: &ComponentConfig) -> Vec<(&'static str, Option<String>)> {
+    let mode = match comp.mode {
+        ComponentMode::Binary => "binary",
+        ComponentMode::Source => "source",
+    };
+    vec![
+        ("mode", Some(mode.to_string())),
+        ("lib", comp.lib.clone()),
+        ("qml", comp.qml.clone()),
+        ("plugin", comp.plugin.clone()),
+        ("headers", comp.headers.clone()),
+        ("bin", comp.bin.clone()),
+        ("root", comp.root.clone()),
+        ("roots", (!comp.roots.is_empty()).then(|| comp.roots.join(", "))),
+        ("cmake_config", comp.cmake_config.clone()),
+        ("pre_link_hook", comp.pre_link_hook.clone()),
+        ("post_link_hook", comp.post_link_hook.clone()),
+        ("priority", (comp.priority != 0).then(|| comp.priority.to_string())),
+        ("build_command", comp.build_command.clone()),
+        ("detected_version", comp.detected_version.clone()),
+    ]
+}
+
+/// Format the changes between two component configs as `- old` / `+ new` lines.
+/// A newly added component (no `before`) shows every set field as an addition.
+fn diff_component(before: Option<&ComponentConfig>, after: &ComponentConfig) -> String {
+    let after_fields = component_fields(after);
+    let before_fields = before.map(component_fields);
+    let mut out = String::new();
+
+    for (i, (field, new)) in after_fields.iter().enumerate() {
+        let old = before_fields.as_ref().and_then(|b| b[i].1.clone());
+        if before_fields.is_some() && old == *new {
+            continue;
+        }
+        if let Some(old) = old {
+            out.push_str(&format!("{}\n", format!("- {}: {}", field, old).red()));
+        }
+        if let Some(new) = new {
+            out.push_str(&format!("{}\n", format!("+ {}: {}", field, new).green()));
+        }
+    }
+    out
+}
+
+/// Record a linked component and persist it, printing a diff when requested.
+///
+/// Returns `false` without touching dev.json when the component is already
+/// linked with identical settings (unless `--force` is given).
+fn commit_link(
+    dev_config: &mut DevConfig,
+    name: &str,
+    mut comp: ComponentConfig,
+    flags: &LinkFlags,
+) -> Result<bool> {
+    let before = dev_config.components.get(name).cloned();
+    if let Some(ref existing) = before {
+        keep_user_settings(existing, &mut comp);
+        comp.linked_at = existing.linked_at.clone();
+    }
+    if let Some(priority) = flags.priority {
+        comp.priority = priority;
+    }
+    if let Some(dir) = &flags.cmake_config {
+        let dir = resolve_abs(dir);
+        if !dir.is_dir() {
+            bail!("CMake config directory not found: {}", dir.display());
+        }
+        comp.cmake_config = Some(dir.to_string_lossy().replace('\\', "/"));
+    }
+    if let Some(ref existing) = before {
+        if !flags.force && *existing == comp {
+            println!("{} Already linked with same paths", "✓".green());
+            return Ok(false);
+        }
+    }
+    comp.linked_at = Some(chrono::Utc::now().to_rfc3339());
+
+    if flags.diff {
+        let diff = diff_component(before.as_ref(), &comp);
+        println!("{} dev.json changes for '{}':", "->".cyan(), name);
+        if diff.is_empty() {
+            println!("  (no changes)");
+        } else {
+            print!("{}", diff);
+        }
+    }
+
+    dev_config.components.insert(name.to_string(), comp);
+    save_linked(dev_config, name)?;
+    Ok(true)
+}
+
+/// Save a link change: pre-link hooks, write dev.json, propagate presets,
+/// then post-link hooks.
+fn save_linked(dev_config: &mut DevConfig, component: &str) -> Result<()> {
+    let comp = dev_config.components.get(component).cloned();
+
+    if let Some(hook) = comp.as_ref().and_then(|c| c.pre_link_hook.as_deref()) {
+        run_hook(hook, component)?;
+    }
+    if let Some(hook) = dev_config.pre_link_hook.as_deref() {
+        run_hook(hook, component)?;
+    }
+
+    dev_config.save()?;
+    reinit_all(dev_config)?;
+
+    if let Some(hook) = dev_config.post_link_hook.as_deref() {
+        run_hook(hook, component)?;
+    }
+    if let Some(hook) = comp.as_ref().and_then(|c| c.post_link_hook.as_deref()) {
+        run_hook(hook, component)?;
+    }
+    Ok(())
+}
+
 /// Resolve a path argument to an absolute, normalized PathBuf
 fn resolve_abs(path: &str) -> PathBuf {
     let p = PathBuf::from(path);
@@ -40,9 +274,82 @@ fn resolve_abs(path: &str) -> PathBuf {
     PathBuf::from(normalize_path(abs))
 }
 
+/// Kind of build output a directory appears to contain
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ComponentType {
+    Plugin,
+    Library,
+    Host,
+    Unknown,
+}
+
+impl ComponentType {
+    /// The `link` subcommand that handles this kind of output
+    fn subcommand(self) -> Option<&'static str> {
+        match self {
+            ComponentType::Plugin => Some("plugin"),
+            ComponentType::Library => Some("component"),
+            ComponentType::Host => Some("host"),
+            ComponentType::Unknown => None,
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            ComponentType::Plugin => "a plugin",
+            ComponentType::Library => "a library component",
+            ComponentType::Host => "the host",
+            ComponentType::Unknown => "an unknown component",
+        }
+    }
+}
+
+/// Guess what a build directory contains from its layout
+fn infer_component_type(path: &Path) -> ComponentType {
+    let host_exe = if cfg!(windows) {
+        "mpf-host.exe"
+    } else {
+        "mpf-host"
+    };
+
+    if path.join("bin").join(host_exe).exists() || path.join(host_exe).exists() {
+        ComponentType::Host
+    } else if path.join("plugins").is_dir() {
+        ComponentType::Plugin
+    } else if path.join("lib").join("cmake").is_dir() {
+        ComponentType::Library
+    } else {
+        ComponentType::Unknown
+    }
+}
+
+/// Print a hint when the build directory looks like a different kind of
+/// component than the link command being used
+fn suggest_link_type(expected: ComponentType, name: &str, path: &Path) {
+    let inferred = infer_component_type(path);
+    if inferred == expected {
+        return;
+    }
+    if let Some(sub) = inferred.subcommand() {
+        let args = if inferred == ComponentType::Host {
+            path.display().to_string()
+        } else {
+            format!("{} {}", name, path.display())
+        };
+        println!(
+            "{} This looks like {} — did you mean `mpf-dev link {} {}`?",
+            "Hint:".yellow(),
+            inferred.describe(),
+            sub,
+            args
+        );
+    }
+}
+
 /// Link a plugin - auto-derives lib, qml, plugin paths from build directory
-fn link_plugin(name: &str, path: &str) -> Result<()> {
+fn link_plugin(name: &str, path: &str, flags: &LinkFlags) -> Result<()> {
     let abs_path = resolve_abs(path);
+    suggest_link_type(ComponentType::Plugin, name, &abs_path);
 
     // Auto-derive paths from plugin build output
     let lib_path = normalize_path(abs_path.join("plugins"));
@@ -52,39 +359,92 @@ fn link_plugin(name: &str, path: &str) -> Result<()> {
     println!("{} Linking plugin '{}'", "->".cyan(), name);
     println!("  Build root: {}", abs_path.display());
     println!("  lib (plugins): {}", lib_path);
+    check_plugin_libs(&lib_path, flags.strict)?;
     println!("  qml: {}", qml_path);
+    check_qml_path(&qml_path, flags.strict)?;
 
     let mut dev_config = DevConfig::load().unwrap_or_default();
 
     // Store as "plugin-<name>" for clarity
-    let component_name = if name.starts_with("plugin-") {
-        name.to_string()
-    } else {
-        format!("plugin-{}", name)
-    };
+    let component_name = plugin_component_name(name);
 
-    dev_config.components.insert(
-        component_name.clone(),
+    if !commit_link(
+        &mut dev_config,
+        &component_name,
         ComponentConfig {
             mode: ComponentMode::Source,
             lib: Some(lib_path),
             qml: Some(qml_path),
             plugin: Some(plugin_path),
-            headers: None,
-            bin: None,
             root: infer_project_root(&abs_path),
+            ..Default::default()
         },
-    );
-    dev_config.save()?;
-    reinit_all(&dev_config)?;
+        flags,
+    )? {
+        return Ok(());
+    }
 
     println!("{} Plugin '{}' linked", "✓".green(), component_name);
     Ok(())
 }
 
+/// First line printed by `mpf-host --version`, or `None` if it fails or
+/// takes longer than two seconds
+async fn host_version(exe: &Path) -> Option<String> {
+    let output = tokio::process::Command::new(exe)
+        .arg("--version")
+        .kill_on_drop(true)
+        .output();
+    let output = tokio::time::timeout(Duration::from_secs(2), output)
+        .await
+        .ok()?
+        .ok()?;
+    let text = if output.stdout.is_empty() {
+        output.stderr
+    } else {
+        output.stdout
+    };
+    String::from_utf8_lossy(&text)
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .map(str::to_string)
+}
+
+/// Check the host executable and report its version, if it runs
+async fn probe_host(exe: &Path) -> Option<String> {
+    if !exe.is_file() {
+        println!(
+            "  {} binary not found yet — build the project first",
+            "⚠".yellow()
+        );
+        return None;
+    }
+    if !config::is_executable(exe) {
+        let fix = if cfg!(windows) {
+            "Unblock the file in its Properties dialog, or rebuild it".to_string()
+        } else {
+            format!("Fix with: chmod +x {}", exe.display())
+        };
+        println!("  {} {} is not executable. {}", "⚠".yellow(), exe.display(), fix);
+        return None;
+    }
+    let version = host_version(exe).await;
+    match &version {
+        Some(v) => println!("  version: {}", v),
+        None => println!(
+            "  {} `{} --version` failed or timed out",
+            "⚠".yellow(),
+            exe.display()
+        ),
+    }
+    version
+}
+
 /// Link host - auto-derives bin, qml paths from build directory
-fn link_host(path: &str) -> Result<()> {
+async fn link_host(path: &str, flags: &LinkFlags) -> Result<()> {
     let abs_path = resolve_abs(path);
+    suggest_link_type(ComponentType::Host, "host", &abs_path);
 
     let host_exe = if cfg!(windows) {
         "mpf-host.exe"
@@ -111,34 +471,86 @@ fn link_host(path: &str) -> Result<()> {
     println!("{} Linking host", "->".cyan());
     println!("  Build root: {}", abs_path.display());
     println!("  bin: {}", bin_path);
+    let detected_version = probe_host(&Path::new(&bin_path).join(host_exe)).await;
     println!("  qml: {}", qml_path);
 
     let mut dev_config = DevConfig::load().unwrap_or_default();
-    dev_config.components.insert(
-        "host".to_string(),
+    if !commit_link(
+        &mut dev_config,
+        "host",
         ComponentConfig {
             mode: ComponentMode::Source,
-            lib: None,
             qml: Some(qml_path),
-            plugin: None,
-            headers: None,
             bin: Some(bin_path),
             root: infer_project_root(&abs_path),
+            detected_version,
+            ..Default::default()
         },
-    );
-    dev_config.save()?;
-    reinit_all(&dev_config)?;
+        flags,
+    )? {
+        return Ok(());
+    }
 
     println!("{} Host linked", "✓".green());
     Ok(())
 }
 
+/// Version declared by a local SDK's CMake package: `set(MPF_VERSION ...)`
+/// in MPFConfig.cmake, else `PACKAGE_VERSION` from MPFConfigVersion.cmake
+fn local_sdk_version(cmake_dir: &Path) -> Option<String> {
+    let find = |file: &str, var: &str| {
+        let content = fs::read_to_string(cmake_dir.join(file)).ok()?;
+        let prefix = format!("set({}", var);
+        content.lines().find_map(|line| {
+            let rest = line.trim().strip_prefix(&prefix)?;
+            // Must be the whole variable name, not a prefix of a longer one
+            if !rest.starts_with(char::is_whitespace) {
+                return None;
+            }
+            let value = rest.trim().trim_end_matches(')').trim().trim_matches('"');
+            (!value.is_empty()).then(|| value.to_string())
+        })
+    };
+    find("MPFConfig.cmake", "MPF_VERSION")
+        .or_else(|| find("MPFConfigVersion.cmake", "PACKAGE_VERSION"))
+}
+
+/// Warn when the local SDK's major version differs from the installed SDK
+fn check_sdk_version(cmake_dir: &Path, dev_config: &DevConfig) {
+    let Some(local) = local_sdk_version(cmake_dir) else {
+        println!(
+            "{} Could not read the local SDK version from {}",
+            "Note:".yellow(),
+            cmake_dir.display()
+        );
+        return;
+    };
+    let Some(installed) = dev_config.sdk_version.clone().or_else(config::current_version) else {
+        return;
+    };
+    let (Some(local_v), Some(installed_v)) = (parse_semver(&local), parse_semver(&installed)) else {
+        return;
+    };
+    if local_v.major != installed_v.major {
+        println!();
+        println!(
+            "{}",
+            "⚠ Local SDK major version mismatch".yellow().bold()
+        );
+        println!("  Local SDK:     {}", local.yellow());
+        println!("  Installed SDK: {}", installed);
+        println!("  Plugins and host built against different major versions may be ABI-incompatible.");
+        println!("  Pass --ignore-version to silence this warning.");
+        println!();
+    }
+}
+
 /// Link a local SDK install directory for development
 ///
 /// The path should point to the cmake install prefix of a locally built SDK,
 /// which must contain lib/cmake/MPF/MPFConfig.cmake and include/mpf/.
 /// This overrides ~/.mpf-sdk/current when generating CMakeUserPresets.json.
-fn link_sdk(path: &str) -> Result<()> {
+fn link_sdk(path: &str, ignore_version: bool, flags: &LinkFlags) -> Result<()> {
     let abs_path = resolve_abs(path);
 
     // Validate: must contain lib/cmake/MPF/MPFConfig.cmake
@@ -173,28 +585,32 @@ fn link_sdk(path: &str) -> Result<()> {
     println!("  headers: {}", headers_path);
 
     let mut dev_config = DevConfig::load().unwrap_or_default();
-    dev_config.components.insert(
-        "sdk".to_string(),
+    if !ignore_version {
+        check_sdk_version(cmake_config.parent().unwrap(), &dev_config);
+    }
+    if !commit_link(
+        &mut dev_config,
+        "sdk",
         ComponentConfig {
             mode: ComponentMode::Source,
             lib: Some(lib_path),
-            qml: None,
-            plugin: None,
             headers: Some(headers_path),
-            bin: None,
             root: None, // SDK has no project root to re-init
+            ..Default::default()
         },
-    );
-    dev_config.save()?;
-    reinit_all(&dev_config)?;
+        flags,
+    )? {
+        return Ok(());
+    }
 
     println!("{} SDK linked for local development", "✓".green());
     Ok(())
 }
 
 /// Link a library component (ui-components, http-client, etc.)
-fn link_component(name: &str, path: &str) -> Result<()> {
+fn link_component(name: &str, path: &str, flags: &LinkFlags) -> Result<()> {
     let abs_path = resolve_abs(path);
+    suggest_link_type(ComponentType::Library, name, &abs_path);
 
     // Auto-derive paths based on component type
     let lib_path = if abs_path.join("lib").exists() {
@@ -240,28 +656,34 @@ fn link_component(name: &str, path: &str) -> Result<()> {
     if let Some(ref p) = headers_path {
         println!("  headers: {}", p);
     }
+    if let Some(ref p) = qml_path {
+        check_qml_path(p, flags.strict)?;
+    }
 
     let mut dev_config = DevConfig::load().unwrap_or_default();
-    dev_config.components.insert(
-        name.to_string(),
+    if !commit_link(
+        &mut dev_config,
+        name,
         ComponentConfig {
             mode: ComponentMode::Source,
             lib: lib_path,
             qml: qml_path,
-            plugin: None,
             headers: headers_path,
             bin: bin_path,
             root: infer_project_root(&abs_path),
+            ..Default::default()
         },
-    );
-    dev_config.save()?;
-    reinit_all(&dev_config)?;
+        flags,
+    )? {
+        return Ok(());
+    }
 
     println!("{} Component '{}' linked", "✓".green(), name);
     Ok(())
 }
 
 /// Link command: register component for source development (legacy interface)
+#[allow(clippy::too_many_arguments)]
 fn link(
     component: &str,
     lib: Option<String>,
@@ -270,6 +692,7 @@ fn link(
     headers: Option<String>,
     bin: Option<String>,
     host: Option<String>,
+    flags: &LinkFlags,
 ) -> Result<()> {
     // Warn if unknown component
     if !config::is_known_component(component) {
@@ -374,6 +797,18 @@ fn link(
     let final_qml = resolve(qml).or(derived_host_qml).or(derived_qml);
     let final_bin = resolve(bin).or(derived_bin);
 
+    // Manual links skip auto-derivation; point out when the build root of
+    // --lib matches one of the typed link commands
+    if plugin.is_none() && host.is_none() {
+        if let Some(root) = final_lib.as_deref().and_then(|p| Path::new(p).parent()) {
+            suggest_link_type(ComponentType::Unknown, component, root);
+        }
+    }
+
+    if let Some(ref p) = final_qml {
+        check_qml_path(p, flags.strict)?;
+    }
+
     let comp_config = ComponentConfig {
         mode: ComponentMode::Source,
         lib: final_lib,
@@ -382,13 +817,12 @@ fn link(
         headers: resolve(headers),
         bin: final_bin,
         root: None, // Manual link — user can run init to set root
+        ..Default::default()
     };
 
-    dev_config
-        .components
-        .insert(component.to_string(), comp_config.clone());
-    dev_config.save()?;
-    reinit_all(&dev_config)?;
+    if !commit_link(&mut dev_config, component, comp_config.clone(), flags)? {
+        return Ok(());
+    }
 
     println!(
         "{} Component '{}' linked for source development",
@@ -415,49 +849,403 @@ fn link(
     Ok(())
 }
 
-/// Unlink command: remove component from source development
-pub fn unlink(component: &str) -> Result<()> {
+/// Unlink command: remove components from source development
+pub fn unlink(components: &[&str], dry_run: bool, force: bool) -> Result<()> {
     let mut dev_config = DevConfig::load()?;
 
-    if component == "all" {
-        if dev_config.components.is_empty() {
-            println!("{} 没有已链接的组件", "Note:".yellow());
-            return Ok(());
+    let all = components.contains(&"all");
+    if all && dev_config.components.is_empty() {
+        println!("{} 没有已链接的组件", "Note:".yellow());
+        return Ok(());
+    }
+
+    // Resolve names to dev.json keys: exact match first, then plugin- prefix
+    let mut to_remove: Vec<String> = Vec::new();
+    let mut not_linked = Vec::new();
+    if all {
+        let mut names: Vec<String> = dev_config.components.keys().cloned().collect();
+        names.sort();
+        to_remove = names;
+    } else {
+        for &component in components {
+            let with_prefix = format!("plugin-{}", component);
+            let key = if dev_config.components.contains_key(component) {
+                component.to_string()
+            } else if dev_config.components.contains_key(&with_prefix) {
+                with_prefix
+            } else {
+                not_linked.push(component);
+                continue;
+            };
+            if !to_remove.contains(&key) {
+                to_remove.push(key);
+            }
+        }
+    }
+
+    // Locked components stay linked unless --force is given
+    let mut locked = Vec::new();
+    if !force {
+        to_remove.retain(|name| {
+            if dev_config.components[name].locked {
+                locked.push(name.clone());
+                false
+            } else {
+                true
+            }
+        });
+    }
+    let warn_locked = || {
+        for name in &locked {
+            println!(
+                "{} Component '{}' is locked; skipped (use --force or `mpf-dev unlock {}`)",
+                "Warning:".yellow(),
+                name,
+                name
+            );
+        }
+    };
+
+    if dry_run {
+        for name in &to_remove {
+            println!("  {} {}", "would remove".yellow(), name);
+        }
+        for component in &not_linked {
+            println!("{} Component '{}' is not linked", "Note:".yellow(), component);
         }
+        warn_locked();
+        let mut remaining: Vec<&String> = dev_config
+            .components
+            .keys()
+            .filter(|k| !to_remove.contains(k))
+            .collect();
+        remaining.sort();
+        println!();
+        println!("{}", "Remaining components:".bold());
+        if remaining.is_empty() {
+            println!("  (none)");
+        }
+        for name in remaining {
+            let comp = &dev_config.components[name];
+            let lock = if comp.locked { " 🔒" } else { "" };
+            println!("  {} ({:?}){}", name.green(), comp.mode, lock);
+        }
+        return Ok(());
+    }
 
-        let count = dev_config.components.len();
+    if all && !to_remove.is_empty() {
+        if !confirm(&format!("Unlink all {} components?", to_remove.len()))? {
+            println!("{} Aborted", "Note:".yellow());
+            return Ok(());
+        }
         println!("{} 正在解除所有组件链接...", "->".cyan());
-        for name in dev_config.components.keys() {
+    }
+
+    for name in &to_remove {
+        dev_config.components.remove(name);
+        if all {
             println!("  - {}", name);
+        } else if name.starts_with("plugin-") && !components.contains(&name.as_str()) {
+            println!("{} Plugin '{}' unlinked", "✓".green(), name.trim_start_matches("plugin-"));
+        } else {
+            println!("{} Component '{}' unlinked", "✓".green(), name);
         }
-        dev_config.components.clear();
+    }
+
+    if !to_remove.is_empty() {
         dev_config.save()?;
-        reinit_all(&dev_config)?;
-        println!("{} 已解除 {} 个组件的链接", "✓".green(), count);
+        reinit_all(&mut dev_config)?;
+    }
+    if all && !to_remove.is_empty() {
+        println!("{} 已解除 {} 个组件的链接", "✓".green(), to_remove.len());
+    }
+    warn_locked();
+
+    for component in not_linked {
+        println!(
+            "{} Component '{}' was not linked",
+            "Note:".yellow(),
+            component
+        );
+    }
+    Ok(())
+}
+
+/// Resolve a component argument to its dev.json key (`orders` → `plugin-orders`)
+fn linked_key(dev_config: &DevConfig, component: &str) -> Result<String> {
+    let with_prefix = format!("plugin-{}", component);
+    if dev_config.components.contains_key(component) {
+        Ok(component.to_string())
+    } else if dev_config.components.contains_key(&with_prefix) {
+        Ok(with_prefix)
+    } else {
+        bail!("Component '{}' is not linked", component);
+    }
+}
+
+/// Lock/unlock command: protect a linked component from `unlink`
+pub fn set_locked(component: &str, locked: bool) -> Result<()> {
+    let mut dev_config = DevConfig::load()?;
+    let key = linked_key(&dev_config, component)?;
+
+    let comp = dev_config.components.get_mut(&key).unwrap();
+    if comp.locked == locked {
+        println!(
+            "{} Component '{}' is already {}",
+            "Note:".yellow(),
+            key,
+            if locked { "locked" } else { "unlocked" }
+        );
         return Ok(());
     }
+    comp.locked = locked;
+    dev_config.save()?;
 
-    // Try exact match first
-    if dev_config.components.remove(component).is_some() {
-        dev_config.save()?;
-        reinit_all(&dev_config)?;
-        println!("{} Component '{}' unlinked", "✓".green(), component);
+    if locked {
+        println!("{} Component '{}' locked 🔒", "✓".green(), key);
+    } else {
+        println!("{} Component '{}' unlocked", "✓".green(), key);
+    }
+    Ok(())
+}
+
+/// Add-root command: register another project that uses a component so
+/// reinit_all regenerates its presets too
+pub fn add_root(component: &str, project_dir: &str) -> Result<()> {
+    let mut dev_config = DevConfig::load()?;
+    let key = linked_key(&dev_config, component)?;
+
+    let dir = resolve_abs(project_dir);
+    if !dir.is_dir() {
+        bail!("Project directory not found: {}", dir.display());
+    }
+    let root = normalize_path(dir);
+    let key_of = |r: &str| r.to_lowercase().replace('\\', "/");
+    let same = |r: &String| key_of(r) == key_of(&root);
+
+    let comp = dev_config.components.get_mut(&key).unwrap();
+    if comp.all_roots().any(same) {
+        println!(
+            "{} {} is already a root of '{}'",
+            "Note:".yellow(),
+            root,
+            key
+        );
         return Ok(());
     }
+    comp.roots.push(root.clone());
+    dev_config.save()?;
 
-    // Try with plugin- prefix
-    let with_prefix = format!("plugin-{}", component);
-    if dev_config.components.remove(&with_prefix).is_some() {
-        dev_config.save()?;
-        reinit_all(&dev_config)?;
-        println!("{} Plugin '{}' unlinked", "✓".green(), component);
+    println!("{} Added root {} to '{}'", "✓".green(), root, key);
+    reinit_all(&mut dev_config)
+}
+
+/// Enable/disable command: toggle a component without losing its paths
+pub fn set_enabled(component: &str, enabled: bool) -> Result<()> {
+    let mut dev_config = DevConfig::load()?;
+    let key = linked_key(&dev_config, component)?;
+
+    let comp = dev_config.components.get_mut(&key).unwrap();
+    if comp.enabled == enabled {
+        println!(
+            "{} Component '{}' is already {}",
+            "Note:".yellow(),
+            key,
+            if enabled { "enabled" } else { "disabled" }
+        );
+        return Ok(());
+    }
+    comp.enabled = enabled;
+    dev_config.save()?;
+
+    if enabled {
+        println!("{} Component '{}' enabled", "✓".green(), key);
+    } else {
+        println!("{} Component '{}' disabled", "✓".green(), key);
+    }
+    reinit_all(&mut dev_config)
+}
+
+/// Rename command: move a linked component to a new dev.json key
+pub fn rename_component(from: &str, to: &str) -> Result<()> {
+    let mut dev_config = DevConfig::load()?;
+    let old_key = linked_key(&dev_config, from)?;
+
+    // Plugins keep the "plugin-" prefix used by `link plugin`
+    let new_key = if old_key.starts_with("plugin-") && !to.starts_with("plugin-") {
+        format!("plugin-{}", to)
+    } else {
+        to.to_string()
+    };
+    if new_key == old_key {
+        println!("{} '{}' already has that name", "Note:".yellow(), old_key);
         return Ok(());
     }
+    if dev_config.components.contains_key(&new_key) {
+        bail!(
+            "Component '{}' already exists. Unlink it first or pick another name.",
+            new_key
+        );
+    }
+
+    let comp = dev_config.components.remove(&old_key).unwrap();
+    dev_config.components.insert(new_key.clone(), comp);
+    // Environment overrides follow the component
+    for overrides in dev_config.environments.values_mut() {
+        if let Some(comp) = overrides.remove(&old_key) {
+            overrides.insert(new_key.clone(), comp);
+        }
+    }
+    dev_config.save()?;
 
     println!(
-        "{} Component '{}' was not linked",
-        "Note:".yellow(),
-        component
+        "{} Renamed component {} → {}",
+        "✓".green(),
+        old_key.yellow(),
+        new_key.green()
     );
+    reinit_all(&mut dev_config)
+}
+
+/// Manifest file that marks a plugin build output for `discover`
+const PLUGIN_MANIFEST: &str = "mpf-plugin.json";
+
+/// Directories never worth descending into while looking for manifests
+const DISCOVER_SKIP_DIRS: &[&str] = &[".git", "node_modules", "CMakeFiles"];
+
+/// Contents of an mpf-plugin.json manifest; paths are relative to the file
+#[derive(Deserialize)]
+struct PluginManifest {
+    name: String,
+    #[serde(default)]
+    version: Option<String>,
+    lib: String,
+    #[serde(default)]
+    qml: Option<String>,
+}
+
+/// A plugin found by `discover_plugins`, with absolute paths
+pub(super) struct DiscoveredPlugin {
+    pub name: String,
+    pub version: Option<String>,
+    /// Directory holding the manifest
+    pub root: String,
+    pub lib: String,
+    pub qml: Option<String>,
+}
+
+/// Recursively find mpf-plugin.json manifests under `root`.
+///
+/// Unreadable or malformed manifests are reported on stderr and skipped.
+pub(super) fn discover_plugins(root: &Path) -> Vec<DiscoveredPlugin> {
+    let mut found = Vec::new();
+    let manifest = root.join(PLUGIN_MANIFEST);
+    if manifest.is_file() {
+        match read_manifest(&manifest) {
+            Ok(plugin) => found.push(plugin),
+            Err(e) => eprintln!("{} {}: {:#}", "Warning:".yellow(), manifest.display(), e),
+        }
+    }
+
+    let mut dirs: Vec<PathBuf> = fs::read_dir(root)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
+                .filter(|e| {
+                    let name = e.file_name();
+                    !DISCOVER_SKIP_DIRS.iter().any(|skip| name == *skip)
+                })
+                .map(|e| e.path())
+                .collect()
+        })
+        .unwrap_or_default();
+    dirs.sort();
+    for dir in dirs {
+        found.extend(discover_plugins(&dir));
+    }
+    found
+}
+
+fn read_manifest(path: &Path) -> Result<DiscoveredPlugin> {
+    let content = fs::read_to_string(path)?;
+    let manifest: PluginManifest =
+        serde_json::from_str(&content).context("Invalid plugin manifest")?;
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let abs = |p: &str| normalize_path(dir.join(p));
+    Ok(DiscoveredPlugin {
+        name: manifest.name,
+        version: manifest.version,
+        root: normalize_path(dir.to_path_buf()),
+        lib: abs(&manifest.lib),
+        qml: manifest.qml.as_deref().map(abs),
+    })
+}
+
+/// Component name a plugin is stored under ("plugin-<name>")
+fn plugin_component_name(name: &str) -> String {
+    if name.starts_with("plugin-") {
+        name.to_string()
+    } else {
+        format!("plugin-{}", name)
+    }
+}
+
+/// Discover command: list manifests under a directory and link the chosen ones
+pub fn discover(directory: &str, yes: bool, auto: bool) -> Result<()> {
+    let root = resolve_abs(directory);
+    if !root.is_dir() {
+        bail!("Directory not found: {}", root.display());
+    }
+
+    let mut dev_config = DevConfig::load().unwrap_or_default();
+    if auto {
+        let root_str = normalize_path(root.clone());
+        if !dev_config.auto_discover_paths.contains(&root_str) {
+            dev_config.auto_discover_paths.push(root_str.clone());
+            dev_config.save()?;
+            println!("{} {} will be scanned on every run", "✓".green(), root_str);
+        }
+    }
+
+    let plugins = discover_plugins(&root);
+    if plugins.is_empty() {
+        println!("No {} found under {}", PLUGIN_MANIFEST, root.display());
+        return Ok(());
+    }
+
+    let mut linked = 0u32;
+    for plugin in plugins {
+        let component_name = plugin_component_name(&plugin.name);
+        println!(
+            "{} {} {}",
+            "->".cyan(),
+            plugin.name.bold(),
+            plugin.version.as_deref().unwrap_or("").dimmed()
+        );
+        println!("  manifest: {}/{}", plugin.root, PLUGIN_MANIFEST);
+        println!("  lib: {}", plugin.lib);
+        if let Some(qml) = &plugin.qml {
+            println!("  qml: {}", qml);
+        }
+
+        if !yes && !confirm(&format!("Link as '{}'?", component_name))? {
+            continue;
+        }
+        let comp = ComponentConfig {
+            mode: ComponentMode::Source,
+            lib: Some(plugin.lib),
+            qml: plugin.qml,
+            plugin: Some(plugin.root.clone()),
+            root: infer_project_root(Path::new(&plugin.root)),
+            ..Default::default()
+        };
+        if commit_link(&mut dev_config, &component_name, comp, &LinkFlags::default())? {
+            println!("{} Plugin '{}' linked", "✓".green(), component_name);
+            linked += 1;
+        }
+    }
+
+    println!("{} Linked {} plugin(s)", "✓".green(), linked);
     Ok(())
 }
@@ -1,32 +1,77 @@
 mod setup;
+mod cache;
 mod link;
 mod init;
 mod run;
 mod workspace;
+mod config;
+mod debug_info;
+mod environment;
 
 // Re-export public command functions
-pub use setup::{setup, versions, use_version};
-pub use link::{link_action, unlink};
-pub use init::init;
-pub use run::{run, env_vars, status};
-pub use workspace::{workspace_init, workspace_build, workspace_run, workspace_status};
+pub use setup::{SDK_ARCHES, SetupOptions, setup, versions, use_version, alias_set, alias_list, sdk_uninstall, sdk_verify, show_changelog};
+pub use cache::{cache_list, cache_clear};
+pub use link::{link_action, unlink, set_enabled, set_locked, rename_component, discover, add_root};
+pub use init::{init, InitOptions};
+pub use run::{run, env_vars, status, status_check, Format, RunOptions};
+pub use config::{config_get, config_list, config_set, cmake_var_set, cmake_var_unset, config_clear_cache, migrate};
+pub use debug_info::debug_info;
+pub use environment::{env_create, env_switch};
+pub use workspace::{
+    workspace_init, workspace_add, workspace_remove, workspace_repos_list, workspace_build, workspace_run, workspace_status, workspace_diff, workspace_lint, workspace_format, workspace_coverage,
+    workspace_checkout, workspace_export, workspace_sync, BuildOptions,
+};
 
 use std::env;
 use std::fs;
+use std::io::{self, Write};
 use std::path::PathBuf;
+use std::process::Command;
+use std::sync::OnceLock;
 
 use anyhow::{bail, Result};
 use colored::*;
+use indexmap::IndexSet;
 
-use crate::config::{self, ComponentMode, DevConfig};
+use crate::config::{ComponentMode, DevConfig};
 
 // ─── Shared constants ────────────────────────────────────────────────────────
 
 const GITHUB_REPO: &str = "QMPF/mpf-release";
 
+// ─── Prompts ─────────────────────────────────────────────────────────────────
+
+/// Ask the user to type `yes` before a destructive action
+fn confirm(prompt: &str) -> Result<bool> {
+    print!("{} [type 'yes' to confirm]: ", prompt);
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(answer.trim() == "yes")
+}
+
+/// Build a command that runs `command` through the platform shell
+fn shell_command(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut c = Command::new("cmd");
+        c.arg("/C").arg(command);
+        c
+    } else {
+        let mut c = Command::new("sh");
+        c.arg("-c").arg(command);
+        c
+    }
+}
+
 // ─── Path utilities ──────────────────────────────────────────────────────────
 
 /// Normalize a path by removing .\ and .. components
+///
+/// Unlike the generated presets, this does not translate WSL2 paths to their
+/// Windows form: the result is stored in dev.json, which also feeds the
+/// Linux-side `run`/`env` environment, where `C:\...` paths would not resolve.
+/// `init` translates the preset cache variables instead (see
+/// `windows_cache_paths`).
 fn normalize_path(p: PathBuf) -> String {
     // Try to canonicalize, fall back to string cleanup
     let result = if let Ok(canonical) = p.canonicalize() {
@@ -37,11 +82,52 @@ fn normalize_path(p: PathBuf) -> String {
         s.replace("\\.\\", "\\").replace("/./", "/")
     };
 
-    // Remove Windows extended path prefix (\\?\)
-    if result.starts_with(r"\\?\") {
-        result[4..].to_string()
-    } else {
-        result
+    // Remove Windows extended path prefix (\\?\); network shares come back
+    // as \\?\UNC\server\share and need their \\ prefix restored
+    match result.strip_prefix(r"\\?\") {
+        Some(stripped) => normalize_unc_path(stripped.to_string()),
+        None => result,
+    }
+}
+
+/// Whether mpf-dev runs inside WSL2 (the kernel release names Microsoft)
+fn is_wsl2() -> bool {
+    static WSL2: OnceLock<bool> = OnceLock::new();
+    *WSL2.get_or_init(|| {
+        cfg!(target_os = "linux")
+            && fs::read_to_string("/proc/sys/kernel/osrelease")
+                .map(|release| release.to_lowercase().contains("microsoft"))
+                .unwrap_or(false)
+    })
+}
+
+/// Windows form of a WSL path (`/mnt/c/dev` → `C:\dev`), from `wslpath -w`
+/// when available, else by mapping `/mnt/<drive>/` directly
+fn wsl2_to_windows_path(path: &str) -> Option<String> {
+    let translated = Command::new("wslpath")
+        .args(["-w", path])
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .filter(|p| !p.is_empty());
+    translated.or_else(|| {
+        let rest = path.strip_prefix("/mnt/")?;
+        let (drive, tail) = rest.split_once('/').unwrap_or((rest, ""));
+        if drive.len() != 1 || !drive.chars().all(|c| c.is_ascii_alphabetic()) {
+            return None;
+        }
+        Some(format!("{}:\\{}", drive.to_uppercase(), tail.replace('/', "\\")))
+    })
+}
+
+/// Turn `UNC\server\share\dir` (an extended-length UNC path with `\\?\`
+/// removed) back into `\\server\share\dir`
+fn normalize_unc_path(s: String) -> String {
+    match s.strip_prefix(r"UNC\") {
+        Some(rest) => format!(r"\\{}", rest),
+        None => s,
     }
 }
 
@@ -51,32 +137,119 @@ fn normalize_path(p: PathBuf) -> String {
 /// 1. Parent of build_path (convention: <source>/build -> <source>)
 /// 2. Current working directory
 ///
-/// Returns Some(normalized_path) if a CMakeLists.txt is found, None otherwise.
+/// Returns Some(normalized_path) if the project root marker (any file
+/// recognised by `infer_build_system` unless `project_root_marker` is set in
+/// dev.json) is found, None otherwise.
 fn infer_project_root(build_path: &std::path::Path) -> Option<String> {
-    // Walk up to 3 levels to find a directory containing CMakeLists.txt
+    let dev_config = DevConfig::load().unwrap_or_default();
+    let is_root = |dir: &std::path::Path| match dev_config.project_root_marker() {
+        Some(marker) => dir.join(marker).exists(),
+        None => infer_build_system(dir) != BuildSystem::Unknown,
+    };
+
+    // Walk up `project_root_search_depth` levels (default 3) to find the marker.
     // Handles both <source>/build and <source>/build/install conventions
     let mut current = Some(build_path);
-    for _ in 0..3 {
+    for _ in 0..dev_config.project_root_search_depth() {
         current = current.and_then(|p| p.parent());
         if let Some(dir) = current {
-            if dir.join("CMakeLists.txt").exists() {
+            if is_root(dir) {
                 return Some(normalize_path(dir.to_path_buf()));
             }
         }
     }
     // Try CWD
     if let Ok(cwd) = env::current_dir() {
-        if cwd.join("CMakeLists.txt").exists() {
+        if is_root(&cwd) {
             return Some(normalize_path(cwd));
         }
     }
     None
 }
 
+/// Build system used by a project directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BuildSystem {
+    CMake,
+    QMake,
+    Meson,
+    Unknown,
+}
+
+/// Detect a project's build system from the files in its root.
+///
+/// CMake wins when several are present, since it is what mpf-dev targets
+/// first.
+fn infer_build_system(path: &std::path::Path) -> BuildSystem {
+    if path.join("CMakeLists.txt").exists() {
+        return BuildSystem::CMake;
+    }
+    if path.join("meson.build").exists() {
+        return BuildSystem::Meson;
+    }
+    let has_pro = fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .any(|e| e.path().extension().is_some_and(|ext| ext == "pro"))
+        })
+        .unwrap_or(false);
+    if has_pro {
+        BuildSystem::QMake
+    } else {
+        BuildSystem::Unknown
+    }
+}
+
 // ─── Tool detection ──────────────────────────────────────────────────────────
 
-/// Try to detect Qt installation path
+/// Compiler subdirectories of an aqtinstall/Qt installer tree, most
+/// appropriate for this platform first
+fn qt_arch_preference() -> &'static [&'static str] {
+    if cfg!(windows) {
+        &["mingw_64", "llvm-mingw_64", "msvc2022_64", "msvc2019_64"]
+    } else if cfg!(target_os = "macos") {
+        &["macos", "clang_64"]
+    } else {
+        &["gcc_64", "linux_gcc_64", "clang_64"]
+    }
+}
+
+/// Find the newest Qt 6 kit under an aqtinstall-style root
+/// (`<root>/6.x.y/<arch>`)
+fn detect_qt_in_root(root: &std::path::Path) -> Option<String> {
+    let mut versions: Vec<(Vec<u32>, PathBuf)> = fs::read_dir(root)
+        .ok()?
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let parts: Vec<u32> = name
+                .split('.')
+                .map(|p| p.parse().ok())
+                .collect::<Option<_>>()?;
+            (parts.len() == 3 && parts[0] == 6).then(|| (parts, entry.path()))
+        })
+        .collect();
+    versions.sort_by(|a, b| b.0.cmp(&a.0));
+
+    versions.iter().find_map(|(_, dir)| {
+        qt_arch_preference()
+            .iter()
+            .map(|arch| dir.join(arch))
+            .find(|p| p.is_dir())
+            .map(|p| p.to_string_lossy().to_string())
+    })
+}
+
+/// Try to detect Qt installation path.
+///
+/// Detection may spawn qmake/cmake, so the result is computed once per process.
 fn detect_qt_path() -> Option<String> {
+    static QT_PATH: OnceLock<Option<String>> = OnceLock::new();
+    QT_PATH.get_or_init(detect_qt_path_uncached).clone()
+}
+
+fn detect_qt_path_uncached() -> Option<String> {
     // Check environment first
     if let Ok(qt_dir) = std::env::var("QT_DIR") {
         return Some(qt_dir);
@@ -84,6 +257,12 @@ fn detect_qt_path() -> Option<String> {
     if let Ok(qt_dir) = std::env::var("Qt6_DIR") {
         return Some(qt_dir);
     }
+    // Explicit aqtinstall output directory
+    if let Ok(aqt_dir) = std::env::var("AQTINSTALL_TARGET_DIR") {
+        if let Some(qt) = detect_qt_in_root(std::path::Path::new(&aqt_dir)) {
+            return Some(qt);
+        }
+    }
 
     // Check common paths
     #[cfg(windows)]
@@ -109,9 +288,223 @@ fn detect_qt_path() -> Option<String> {
                 return Some(path.to_string());
             }
         }
+        // Distribution packages (apt install qt6-base-dev, dnf, pacman)
+        let distro_paths = [
+            "/usr/lib/x86_64-linux-gnu/qt6",
+            "/usr/lib/aarch64-linux-gnu/qt6",
+            "/usr/lib64/qt6",
+            "/usr/share/qt6",
+        ];
+        for path in distro_paths {
+            if std::path::Path::new(path).exists() {
+                return Some(path.to_string());
+            }
+        }
+        if let Some(qt) = detect_qt_via_pkg_config().or_else(detect_qt_via_dpkg) {
+            return Some(qt);
+        }
     }
 
-    None
+    // aqtinstall / online installer default: ~/Qt/6.x.y/<arch>
+    if let Some(qt) = dirs::home_dir().and_then(|home| detect_qt_in_root(&home.join("Qt"))) {
+        return Some(qt);
+    }
+
+    // Qt tools on PATH, package managers, then let CMake itself look
+    detect_qt_via_qmake()
+        .or_else(detect_qt_via_qt_cmake)
+        .or_else(detect_qt_vcpkg)
+        .or_else(detect_qt_conan)
+        .or_else(detect_qt_via_cmake_query)
+}
+
+/// Qt from pkg-config: `<libdir>/qt6` when the distribution uses that
+/// layout, else the Qt6Core prefix
+#[cfg(unix)]
+fn detect_qt_via_pkg_config() -> Option<String> {
+    let variable = |name: &str| {
+        let output = std::process::Command::new("pkg-config")
+            .args([&format!("--variable={}", name), "Qt6Core"])
+            .stderr(std::process::Stdio::null())
+            .output()
+            .ok()
+            .filter(|o| o.status.success())?;
+        let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!value.is_empty()).then_some(value)
+    };
+    if let Some(qt6) = variable("libdir").map(|lib| PathBuf::from(lib).join("qt6")) {
+        if qt6.is_dir() {
+            return Some(qt6.to_string_lossy().into_owned());
+        }
+    }
+    variable("prefix")
+}
+
+/// Qt directory installed by the Debian/Ubuntu qt6-base-dev package
+#[cfg(unix)]
+fn detect_qt_via_dpkg() -> Option<String> {
+    let output = std::process::Command::new("dpkg")
+        .args(["-L", "qt6-base-dev"])
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .find(|line| line.starts_with("/usr/lib/") && line.ends_with("/qt6"))
+        .filter(|dir| std::path::Path::new(dir).is_dir())
+        .map(str::to_string)
+}
+
+/// Qt 6 prefix from `qmake --version` ("Using Qt version 6.8.3 in <libdir>").
+///
+/// qmake reports the library directory, so the prefix is what precedes `/lib`.
+fn detect_qt_via_qmake() -> Option<String> {
+    ["qmake6", "qmake"].iter().find_map(|program| {
+        let output = std::process::Command::new(program)
+            .arg("--version")
+            .stderr(std::process::Stdio::null())
+            .output()
+            .ok()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let rest = stdout
+            .lines()
+            .find_map(|line| line.split_once("Qt version ").map(|(_, rest)| rest))?;
+        let (version, lib_dir) = rest.split_once(" in ")?;
+        if !version.starts_with("6.") {
+            return None;
+        }
+        let lib_dir = lib_dir.trim().replace('\\', "/");
+        let idx = lib_dir.rfind("/lib")?;
+        Some(lib_dir[..idx].to_string())
+    })
+}
+
+/// Qt 6 prefix from the `qt-cmake` wrapper on PATH (`<prefix>/bin/qt-cmake`)
+fn detect_qt_via_qt_cmake() -> Option<String> {
+    let name = if cfg!(windows) { "qt-cmake.bat" } else { "qt-cmake" };
+    let status = std::process::Command::new(name)
+        .arg("--version")
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .ok()?;
+    if !status.success() {
+        return None;
+    }
+    env::split_paths(&env::var_os("PATH")?)
+        .map(|dir| dir.join(name))
+        .find(|p| p.is_file())
+        .and_then(|p| p.canonicalize().ok())
+        .and_then(|p| p.parent()?.parent().map(|prefix| normalize_path(prefix.to_path_buf())))
+}
+
+/// Qt from vcpkg: `$VCPKG_ROOT/installed/<triplet>` when it has share/Qt6
+fn detect_qt_vcpkg() -> Option<String> {
+    let root = PathBuf::from(std::env::var("VCPKG_ROOT").ok()?);
+    let triplet = if cfg!(windows) {
+        "x64-windows"
+    } else if cfg!(target_os = "macos") {
+        "arm64-osx"
+    } else {
+        "x64-linux"
+    };
+    let installed = root.join("installed").join(triplet);
+    installed
+        .join("share")
+        .join("Qt6")
+        .is_dir()
+        .then(|| installed.to_string_lossy().to_string())
+}
+
+/// Qt from the Conan 2 cache: `~/.conan2/p/qt*/p` when it has lib/cmake/Qt6
+fn detect_qt_conan() -> Option<String> {
+    let packages = dirs::home_dir()?.join(".conan2").join("p");
+    fs::read_dir(packages)
+        .ok()?
+        .flatten()
+        .filter(|e| e.file_name().to_string_lossy().starts_with("qt"))
+        .map(|e| e.path().join("p"))
+        .find(|p| p.join("lib").join("cmake").join("Qt6").is_dir())
+        .map(|p| p.to_string_lossy().to_string())
+}
+
+/// Ask CMake's package lookup where Qt6 is, deriving the install prefix
+/// from the reported include directory
+fn detect_qt_via_cmake_query() -> Option<String> {
+    let output = std::process::Command::new("cmake")
+        .args([
+            "--find-package",
+            "-DNAME=Qt6",
+            "-DCOMPILER_ID=GNU",
+            "-DLANGUAGE=CXX",
+            "-DMODE=COMPILE",
+        ])
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .filter_map(|flag| flag.strip_prefix("-I"))
+        .find_map(|include| {
+            let include = include.replace('\\', "/");
+            let idx = include.find("/include")?;
+            Some(include[..idx].to_string())
+        })
+}
+
+/// Detect C/C++ compilers: MinGW shipped with Qt first, then MSYS2, then
+/// whatever is on PATH (for Qt from package managers or system packages)
+fn detect_compilers(qt_path: &str) -> Option<(String, String)> {
+    detect_mingw_path(qt_path)
+        .or_else(detect_msys2_mingw_path)
+        .or_else(detect_system_compilers)
+}
+
+/// MSYS2 toolchains under C:\msys64, in order: ucrt64, mingw64, clang64
+fn detect_msys2_mingw_path() -> Option<(String, String)> {
+    if !cfg!(windows) {
+        return None;
+    }
+    let root = std::path::Path::new("C:\\msys64");
+    let candidates = [
+        ("ucrt64", "gcc.exe", "g++.exe"),
+        ("mingw64", "gcc.exe", "g++.exe"),
+        ("clang64", "clang.exe", "clang++.exe"),
+    ];
+    candidates.iter().find_map(|(env, cc, cxx)| {
+        let bin = root.join(env).join("bin");
+        let (cc, cxx) = (bin.join(cc), bin.join(cxx));
+        (cc.exists() && cxx.exists()).then(|| {
+            (
+                cc.to_string_lossy().replace('\\', "/"),
+                cxx.to_string_lossy().replace('\\', "/"),
+            )
+        })
+    })
+}
+
+/// Find gcc/g++ (or clang/clang++) on PATH
+fn detect_system_compilers() -> Option<(String, String)> {
+    let finder = if cfg!(windows) { "where" } else { "which" };
+    let locate = |program: &str| -> Option<String> {
+        let output = std::process::Command::new(finder).arg(program).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .next()
+            .map(|l| l.trim().replace('\\', "/"))
+    };
+
+    [("gcc", "g++"), ("clang", "clang++")]
+        .iter()
+        .find_map(|(cc, cxx)| Some((locate(cc)?, locate(cxx)?)))
 }
 
 /// Try to detect MinGW compiler paths from Qt installation
@@ -155,22 +548,73 @@ fn detect_mingw_path(qt_path: &str) -> Option<(String, String)> {
     None
 }
 
+/// CMake generators accepted by `init --generator` and `workspace init/build --generator`
+pub const CMAKE_GENERATORS: &[&str] = &[
+    "Ninja",
+    "Ninja Multi-Config",
+    "Unix Makefiles",
+    "MinGW Makefiles",
+    "Visual Studio 17 2022",
+    "Xcode",
+];
+
+/// Multi-config generators pick the build type at build time (--config)
+/// rather than through CMAKE_BUILD_TYPE
+fn is_multi_config(generator: &str) -> bool {
+    generator == "Ninja Multi-Config" || generator == "Xcode" || generator.starts_with("Visual Studio")
+}
+
+/// Components whose source builds are found through a CMake `<Package>_DIR` variable
+const COMPONENT_CMAKE_DIR_VARS: &[(&str, &str)] = &[
+    ("ui-components", "MPFUIComponents_DIR"),
+    ("http-client", "MPFHttpClient_DIR"),
+];
+
 /// Map component name to CMake package directory variable name
 fn component_cmake_dir_var(component_name: &str) -> Option<&'static str> {
-    match component_name {
-        "ui-components" => Some("MPFUIComponents_DIR"),
-        "http-client" => Some("MPFHttpClient_DIR"),
-        _ => None,
-    }
+    COMPONENT_CMAKE_DIR_VARS
+        .iter()
+        .find(|(name, _)| *name == component_name)
+        .map(|(_, var)| *var)
 }
 
 // ─── Environment path builder ────────────────────────────────────────────────
 
+/// Environment variables the dynamic loader searches for shared libraries.
+/// On macOS DYLD_FALLBACK_LIBRARY_PATH also covers libraries the binaries
+/// don't locate by install name.
+fn library_path_vars() -> &'static [&'static str] {
+    if cfg!(windows) {
+        &["PATH"]
+    } else if cfg!(target_os = "macos") {
+        &["DYLD_LIBRARY_PATH", "DYLD_FALLBACK_LIBRARY_PATH"]
+    } else {
+        &["LD_LIBRARY_PATH"]
+    }
+}
+
+/// Environment path strings computed from the SDK and dev.json
+struct EnvPaths {
+    /// SDK root (MPF_SDK_ROOT)
+    sdk_root: String,
+    /// Library search path (see `library_path_vars`)
+    lib_path: String,
+    /// QML_IMPORT_PATH
+    qml_path: String,
+    /// QT_PLUGIN_PATH
+    plugin_path: String,
+    /// MPF_PLUGIN_PATH (empty if no plugins are linked)
+    mpf_plugin_path: String,
+    /// mpf-host executable to launch
+    host_path: PathBuf,
+    /// MPF_QML_PATH override when host is linked
+    host_qml_path: Option<String>,
+}
+
 /// Build environment path strings
-/// Returns: (sdk_root, lib_path, qml_path, qt_plugin_path, mpf_plugin_path, host_path, host_qml_path)
-fn build_env_paths() -> Result<(String, String, String, String, String, PathBuf, Option<String>)> {
+fn build_env_paths() -> Result<EnvPaths> {
     let dev_config = DevConfig::load().unwrap_or_default();
-    let sdk = config::current_link();
+    let sdk = crate::config::current_link();
 
     if !sdk.exists() {
         bail!("No SDK version set. Run 'mpf-dev setup' first.");
@@ -179,18 +623,19 @@ fn build_env_paths() -> Result<(String, String, String, String, String, PathBuf,
     // SDK root path (used by mpf-host to find default paths)
     let sdk_root = sdk.to_string_lossy().to_string();
 
-    let mut lib_paths: Vec<String> = Vec::new();
-    let mut qml_paths: Vec<String> = Vec::new();
-    let mut plugin_paths: Vec<String> = Vec::new();
-    let mut mpf_plugin_paths: Vec<String> = Vec::new();
+    // Insertion-ordered sets: first occurrence wins, duplicates are dropped
+    let mut lib_paths: IndexSet<String> = IndexSet::new();
+    let mut qml_paths: IndexSet<String> = IndexSet::new();
+    let mut plugin_paths: IndexSet<String> = IndexSet::new();
+    let mut mpf_plugin_paths: IndexSet<String> = IndexSet::new();
     let mut host_bin_override: Option<String> = None;
     let mut host_qml_override: Option<String> = None;
 
     // Source components first (higher priority)
-    for (name, comp) in &dev_config.components {
+    for (name, comp) in dev_config.enabled_components() {
         if comp.mode == ComponentMode::Source {
             if let Some(lib) = &comp.lib {
-                lib_paths.push(lib.clone());
+                lib_paths.insert(lib.clone());
 
                 // On Windows, DLLs may be in a sibling bin/ directory
                 // (MinGW installs: RUNTIME→bin/, ARCHIVE→lib/)
@@ -198,23 +643,20 @@ fn build_env_paths() -> Result<(String, String, String, String, String, PathBuf,
                 if let Some(parent) = lib_path.parent() {
                     let sibling_bin = parent.join("bin");
                     if sibling_bin.is_dir() {
-                        let bin_str = sibling_bin.to_string_lossy().replace('\\', "/");
-                        if !lib_paths.contains(&bin_str) {
-                            lib_paths.push(bin_str);
-                        }
+                        lib_paths.insert(sibling_bin.to_string_lossy().replace('\\', "/"));
                     }
                 }
 
                 // For plugin components (not host/sdk), also add to MPF_PLUGIN_PATH
                 if name != "host" && name != "sdk" {
-                    mpf_plugin_paths.push(lib.clone());
+                    mpf_plugin_paths.insert(lib.clone());
                 }
             }
             if let Some(qml) = &comp.qml {
-                qml_paths.push(qml.clone());
+                qml_paths.insert(qml.clone());
             }
             if let Some(plugin) = &comp.plugin {
-                plugin_paths.push(plugin.clone());
+                plugin_paths.insert(plugin.clone());
             }
 
             // Check for host component bin/qml override
@@ -232,13 +674,30 @@ fn build_env_paths() -> Result<(String, String, String, String, String, PathBuf,
         }
     }
 
+    // Plugins under auto_discover_paths, unless already linked explicitly
+    for dir in &dev_config.auto_discover_paths {
+        for plugin in link::discover_plugins(std::path::Path::new(dir)) {
+            let key = format!("plugin-{}", plugin.name.trim_start_matches("plugin-"));
+            if dev_config.components.contains_key(&key) {
+                continue;
+            }
+            lib_paths.insert(plugin.lib.clone());
+            mpf_plugin_paths.insert(plugin.lib);
+            if let Some(qml) = plugin.qml {
+                qml_paths.insert(qml);
+            }
+            eprintln!("{} Discovered plugin: {}", "->".cyan(), plugin.name);
+        }
+    }
+
     // SDK paths as fallback (include both lib/ and bin/ for Windows DLL discovery)
-    lib_paths.push(sdk.join("lib").to_string_lossy().to_string());
-    lib_paths.push(sdk.join("bin").to_string_lossy().to_string());
-    qml_paths.push(sdk.join("qml").to_string_lossy().to_string());
-    plugin_paths.push(sdk.join("plugins").to_string_lossy().to_string());
+    lib_paths.insert(sdk.join("lib").to_string_lossy().to_string());
+    lib_paths.insert(sdk.join("bin").to_string_lossy().to_string());
+    qml_paths.insert(sdk.join("qml").to_string_lossy().to_string());
+    plugin_paths.insert(sdk.join("plugins").to_string_lossy().to_string());
 
     let sep = if cfg!(windows) { ";" } else { ":" };
+    let join = |paths: IndexSet<String>| paths.into_iter().collect::<Vec<_>>().join(sep);
 
     // Use linked host bin if available, otherwise use SDK's mpf-host
     let host_exe_name = if cfg!(windows) {
@@ -258,13 +717,13 @@ fn build_env_paths() -> Result<(String, String, String, String, String, PathBuf,
         sdk.join("bin").join(host_exe_name)
     };
 
-    Ok((
+    Ok(EnvPaths {
         sdk_root,
-        lib_paths.join(sep),
-        qml_paths.join(sep),
-        plugin_paths.join(sep),
-        mpf_plugin_paths.join(sep),
+        lib_path: join(lib_paths),
+        qml_path: join(qml_paths),
+        plugin_path: join(plugin_paths),
+        mpf_plugin_path: join(mpf_plugin_paths),
         host_path,
-        host_qml_override,
-    ))
+        host_qml_path: host_qml_override,
+    })
 }
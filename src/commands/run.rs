@@ -1,14 +1,18 @@
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use colored::*;
-use std::env;
-use std::process::Command;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
 
-use crate::config::{self, ComponentConfig, ComponentMode, DevConfig};
+use crate::config::{self, ComponentConfig, ComponentMode, DevConfig, KNOWN_COMPONENTS};
 
-use super::{build_env_paths, detect_qt_path};
+use super::{build_env_paths, confirm, detect_qt_path, library_path_vars, EnvPaths};
 
 /// Status command: show current configuration
-pub fn status() -> Result<()> {
+pub fn status(sort: &str, graph: bool) -> Result<()> {
     let dev_config = DevConfig::load().unwrap_or_default();
     let current = config::current_version();
     let sdk_root = config::sdk_root();
@@ -24,23 +28,38 @@ pub fn status() -> Result<()> {
     } else {
         println!("  Version: {}", "not set".red());
     }
-    if let Some(sdk_comp) = dev_config.components.get("sdk") {
-        if sdk_comp.mode == ComponentMode::Source {
-            if let Some(lib) = &sdk_comp.lib {
-                let install_root = std::path::Path::new(lib.as_str())
-                    .parent()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .unwrap_or_default();
-                println!(
-                    "  Local: {} {}",
-                    install_root.green(),
-                    "(overrides current)".dimmed()
-                );
-            }
+    if let Some(env) = &dev_config.active_environment {
+        println!("  Environment: {}", env.cyan());
+    }
+    if let Some(sdk_comp) = dev_config.source_component("sdk") {
+        if let Some(lib) = &sdk_comp.lib {
+            let install_root = std::path::Path::new(lib.as_str())
+                .parent()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_default();
+            println!(
+                "  Local: {} {}",
+                install_root.green(),
+                "(overrides current)".dimmed()
+            );
+        }
+        if let Some(dir) = &sdk_comp.cmake_config {
+            println!("  CMake config: {}", dir);
         }
     }
     println!();
 
+    if graph {
+        println!("{}", "🌳 Dependencies".bold());
+        // Same components as the presets and `run`: overrides applied,
+        // disabled ones marked
+        let components = dev_config.with_active_overrides().components;
+        print!("{}", render_dependency_graph(&components));
+        println!();
+        print_config_location(&dev_config);
+        return Ok(());
+    }
+
     // Group components by type
     let mut host: Option<(&String, &ComponentConfig)> = None;
     let mut plugins: Vec<(&String, &ComponentConfig)> = Vec::new();
@@ -56,11 +75,30 @@ pub fn status() -> Result<()> {
         }
     }
 
+    // Default to name order; "date" shows the most recently linked first
+    for group in [&mut plugins, &mut libs] {
+        if sort == "date" {
+            group.sort_by(|a, b| b.1.linked_at.cmp(&a.1.linked_at).then(a.0.cmp(b.0)));
+        } else {
+            group.sort_by(|a, b| a.0.cmp(b.0));
+        }
+    }
+
     // Host section
     println!("{}", "🖥️  Host".bold());
     if let Some((_, comp)) = host {
         if let Some(bin) = &comp.bin {
-            println!("  {} bin: {}", "✓".green(), bin);
+            println!(
+                "  {} {}{}{} {}",
+                enabled_mark(comp),
+                component_label(&format!("bin: {}", bin), false, comp),
+                lock_mark(comp),
+                priority_mark(comp).dimmed(),
+                linked_at(comp).dimmed()
+            );
+        }
+        if let Some(version) = &comp.detected_version {
+            println!("    version: {}", version);
         }
         if let Some(qml) = &comp.qml {
             println!("    qml: {}", qml);
@@ -82,13 +120,23 @@ pub fn status() -> Result<()> {
     } else {
         for (name, comp) in &plugins {
             let display_name = name.strip_prefix("plugin-").unwrap_or(name);
-            println!("  {} {}", "✓".green(), display_name.bold());
+            println!(
+                "  {} {}{}{} {}",
+                enabled_mark(comp),
+                component_label(display_name, true, comp),
+                lock_mark(comp),
+                priority_mark(comp).dimmed(),
+                linked_at(comp).dimmed()
+            );
             if let Some(lib) = &comp.lib {
                 println!("    lib: {}", lib);
             }
             if let Some(qml) = &comp.qml {
                 println!("    qml: {}", qml);
             }
+            if let Some(dir) = &comp.cmake_config {
+                println!("    cmake config: {}", dir);
+            }
         }
     }
     println!();
@@ -103,7 +151,14 @@ pub fn status() -> Result<()> {
         );
     } else {
         for (name, comp) in &libs {
-            println!("  {} {}", "✓".green(), name.bold());
+            println!(
+                "  {} {}{}{} {}",
+                enabled_mark(comp),
+                component_label(name, true, comp),
+                lock_mark(comp),
+                priority_mark(comp).dimmed(),
+                linked_at(comp).dimmed()
+            );
             if let Some(lib) = &comp.lib {
                 println!("    lib: {}", lib);
             }
@@ -113,21 +168,467 @@ pub fn status() -> Result<()> {
             if let Some(headers) = &comp.headers {
                 println!("    headers: {}", headers);
             }
+            if let Some(dir) = &comp.cmake_config {
+                println!("    cmake config: {}", dir);
+            }
         }
     }
     println!();
 
-    // Config file location
+    let issues = config::validate_config_paths(&dev_config);
+    if !issues.is_empty() {
+        println!("{}", "⚠️  Problems".bold());
+        for issue in &issues {
+            println!("  {} {}", "⚠".yellow(), issue);
+        }
+        println!();
+    }
+
+    print_config_location(&dev_config);
+    Ok(())
+}
+
+/// Config file location section of `status`
+fn print_config_location(dev_config: &DevConfig) {
     println!("{}", "📝 Config".bold());
     println!("  {}", config::dev_config_path().display());
+    if let Some(ts) = &dev_config.last_reinit {
+        println!("  Presets regenerated: {}", format_timestamp(ts).dimmed());
+    }
+}
 
-    Ok(())
+/// Render known and linked components as an ASCII tree rooted at the SDK.
+///
+/// A component with several dependencies appears under each of them.
+fn render_dependency_graph(components: &HashMap<String, ComponentConfig>) -> String {
+    let mut nodes: Vec<&str> = KNOWN_COMPONENTS.to_vec();
+    let mut custom: Vec<&str> = components
+        .keys()
+        .map(String::as_str)
+        .filter(|name| !nodes.contains(name))
+        .collect();
+    custom.sort();
+    nodes.extend(custom);
+
+    let mut out = format!("  {}\n", graph_label("sdk", components));
+    render_graph_children("sdk", &nodes, components, "  ", &mut out);
+    out
+}
+
+fn render_graph_children(
+    parent: &str,
+    nodes: &[&str],
+    components: &HashMap<String, ComponentConfig>,
+    prefix: &str,
+    out: &mut String,
+) {
+    let children: Vec<&str> = nodes
+        .iter()
+        .copied()
+        .filter(|name| config::component_dependencies(name).contains(&parent))
+        .collect();
+    for (i, child) in children.iter().enumerate() {
+        let last = i + 1 == children.len();
+        let branch = if last { "└── " } else { "├── " };
+        out.push_str(&format!("{}{}{}\n", prefix, branch, graph_label(child, components)));
+        let indent = if last { "    " } else { "│   " };
+        render_graph_children(child, nodes, components, &format!("{}{}", prefix, indent), out);
+    }
+}
+
+/// Tree node text: component name and where it currently comes from
+fn graph_label(name: &str, components: &HashMap<String, ComponentConfig>) -> String {
+    match components.get(name) {
+        Some(comp) if !comp.enabled => format!("{} (disabled)", name).dimmed().to_string(),
+        Some(comp) if comp.mode == ComponentMode::Source => {
+            format!("{} {}", name.bold(), "(source)".green())
+        }
+        _ => format!("{} {}", name, "(prebuilt)".dimmed()),
+    }
+}
+
+/// Status --check: plain-text health check for scripts.
+///
+/// Exits 0 when everything is usable, 1 when there are only warnings
+/// (stale linked paths), 2 on errors (no SDK, broken current link, no host).
+pub fn status_check() -> Result<()> {
+    let dev_config = DevConfig::load().unwrap_or_default();
+    let mut errors: Vec<String> = Vec::new();
+    let mut warnings: Vec<String> = Vec::new();
+
+    let has_version = config::current_version().is_some();
+    if !has_version {
+        errors.push("no SDK version set (run `mpf-dev setup`)".to_string());
+    }
+
+    // A broken `current` link is fatal; stale component paths only warn
+    for issue in config::validate_config_paths(&dev_config) {
+        if issue.field_name == "current" {
+            if has_version {
+                errors.push(format!("current SDK link is broken ({})", issue));
+            }
+        } else {
+            warnings.push(issue.to_string());
+        }
+    }
+
+    // Same resolution as build_env_paths, without its progress output
+    let host_exe = if cfg!(windows) {
+        "mpf-host.exe"
+    } else {
+        "mpf-host"
+    };
+    let host_path = dev_config
+        .host_bin()
+        .map(|bin| PathBuf::from(bin).join(host_exe))
+        .unwrap_or_else(|| config::current_link().join("bin").join(host_exe));
+    if !host_path.exists() {
+        errors.push(format!("mpf-host not found: {}", host_path.display()));
+    }
+
+    for w in &warnings {
+        eprintln!("warning: {}", w);
+    }
+    for e in &errors {
+        eprintln!("error: {}", e);
+    }
+
+    let code = if !errors.is_empty() {
+        2
+    } else if !warnings.is_empty() {
+        1
+    } else {
+        0
+    };
+    std::process::exit(code);
+}
+
+/// Show an RFC 3339 timestamp in local time, falling back to the raw string
+fn format_timestamp(ts: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(ts)
+        .map(|t| {
+            t.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|_| ts.to_string())
+}
+
+fn linked_at(comp: &ComponentConfig) -> String {
+    comp.linked_at
+        .as_deref()
+        .map(|ts| format!("(linked {})", format_timestamp(ts)))
+        .unwrap_or_default()
+}
+
+fn enabled_mark(comp: &ComponentConfig) -> ColoredString {
+    if comp.enabled {
+        "✓".green()
+    } else {
+        "○".dimmed()
+    }
+}
+
+/// Component name as shown by `status`; disabled components are muted
+fn component_label(label: &str, bold: bool, comp: &ComponentConfig) -> String {
+    if !comp.enabled {
+        format!("{} (disabled)", label).dimmed().to_string()
+    } else if bold {
+        label.bold().to_string()
+    } else {
+        label.to_string()
+    }
+}
+
+fn priority_mark(comp: &ComponentConfig) -> String {
+    if comp.priority != 0 {
+        format!(" [priority {}]", comp.priority)
+    } else {
+        String::new()
+    }
+}
+
+fn lock_mark(comp: &ComponentConfig) -> &'static str {
+    if comp.locked {
+        " 🔒"
+    } else {
+        ""
+    }
+}
+
+/// Every individual path referenced by the environment that does not exist,
+/// as (variable, path) pairs
+fn missing_env_paths(paths: &EnvPaths) -> Vec<(&'static str, PathBuf)> {
+    let mut vars: Vec<(&'static str, &str)> = vec![
+        ("MPF_SDK_ROOT", &paths.sdk_root),
+        ("LIB_PATH", &paths.lib_path),
+        ("QML_IMPORT_PATH", &paths.qml_path),
+        ("QT_PLUGIN_PATH", &paths.plugin_path),
+        ("MPF_PLUGIN_PATH", &paths.mpf_plugin_path),
+    ];
+    if let Some(ref hqp) = paths.host_qml_path {
+        vars.push(("MPF_QML_PATH", hqp));
+    }
+
+    let mut missing = Vec::new();
+    for (var, value) in vars {
+        if value.is_empty() {
+            continue;
+        }
+        for p in std::env::split_paths(value) {
+            if !p.as_os_str().is_empty() && !p.exists() {
+                missing.push((var, p));
+            }
+        }
+    }
+    if !paths.host_path.exists() {
+        missing.push(("mpf-host", paths.host_path.clone()));
+    }
+    missing
+}
+
+/// Problems with the paths `run`/`env` would use: dev.json issues from
+/// `validate_config_paths`, then anything else missing from the computed
+/// variables (SDK fallbacks, auto-discovered plugins)
+fn path_problems(paths: &EnvPaths) -> Vec<String> {
+    let issues = config::validate_config_paths(&DevConfig::load().unwrap_or_default());
+    let mut problems: Vec<String> = issues.iter().map(ToString::to_string).collect();
+    for (var, p) in missing_env_paths(paths) {
+        if !issues.iter().any(|issue| Path::new(&issue.path) == p) {
+            problems.push(format!("{}: {}", var, p.display()));
+        }
+    }
+    problems
+}
+
+fn report_missing_paths(problems: &[String]) {
+    for problem in problems {
+        eprintln!("  {} {}", "⚠".yellow(), problem);
+    }
+}
+
+/// Env --diff: compare the MPF variables `run` would set against the
+/// current process environment
+fn print_env_diff(paths: &EnvPaths) {
+    let mut computed: Vec<(&str, String)> = vec![
+        ("MPF_SDK_ROOT", paths.sdk_root.clone()),
+        ("QML_IMPORT_PATH", paths.qml_path.clone()),
+    ];
+    for var in library_path_vars() {
+        computed.push((var, paths.lib_path.clone()));
+    }
+    computed.push(("QT_PLUGIN_PATH", paths.plugin_path.clone()));
+    if !paths.mpf_plugin_path.is_empty() {
+        computed.push(("MPF_PLUGIN_PATH", paths.mpf_plugin_path.clone()));
+    }
+    if let Some(ref hqp) = paths.host_qml_path {
+        computed.push(("MPF_QML_PATH", hqp.clone()));
+    }
+
+    println!("{}", "# Environment diff (current shell → mpf-dev run)".bold().cyan());
+    let vars = ["MPF_SDK_ROOT", "QML_IMPORT_PATH"]
+        .into_iter()
+        .chain(library_path_vars().iter().copied())
+        .chain(["QT_PLUGIN_PATH", "MPF_PLUGIN_PATH", "MPF_QML_PATH"]);
+    for var in vars {
+        let old = std::env::var(var).ok();
+        // Windows PATH is prepended to, everything else is replaced
+        let new = computed.iter().find(|(k, _)| *k == var).map(|(_, v)| {
+            if cfg!(windows) && var == "PATH" {
+                format!("{};{}", v, old.as_deref().unwrap_or_default())
+            } else {
+                v.clone()
+            }
+        });
+
+        match (old, new) {
+            (Some(old), Some(new)) if old == new => {
+                println!("  {} {} unchanged", "=".dimmed(), var);
+            }
+            (Some(old), Some(new)) => {
+                println!("  {} {} modified", "~".yellow(), var.yellow());
+                println!("      {} → {}", old.dimmed(), new);
+            }
+            (None, Some(new)) => {
+                println!("  {} {} new variable: {}", "+".green(), var.green(), new);
+            }
+            (Some(old), None) => {
+                println!("  {} {} would be removed (was {})", "-".red(), var.red(), old);
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// Output format of `mpf-dev env`
+#[derive(Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// `export`/`set` lines for the platform shell
+    Shell,
+    /// `KEY=value` lines with values as-is, for `docker run --env-file`
+    Dotenv,
+    /// `KEY=value` lines with special values quoted, for python-dotenv
+    PythonDotenv,
+}
+
+/// Quote a dotenv value when it contains whitespace or characters that
+/// dotenv parsers treat specially
+fn dotenv_value(value: &str) -> String {
+    let needs_quotes = value.is_empty()
+        || value
+            .chars()
+            .any(|c| c.is_whitespace() || "#\"'$`\\=;".contains(c));
+    if needs_quotes {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// `KEY=value` lines (no `export`/`set`) under a header with the
+/// generation time. Path lists keep their separators and the library path
+/// is a plain `LD_LIBRARY_PATH=` / `DYLD_LIBRARY_PATH=` / `PATH=` entry.
+///
+/// Docker reads everything after `=` literally, so values are only quoted
+/// for python-dotenv (`quote`).
+fn dotenv_content(vars: &[(&str, &str)], quote: bool) -> String {
+    let mut content = format!(
+        "# MPF Development Environment\n# Generated by mpf-dev {} at {}\n",
+        env!("CARGO_PKG_VERSION"),
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S")
+    );
+    for (key, value) in vars {
+        let value = if quote {
+            dotenv_value(value)
+        } else {
+            value.to_string()
+        };
+        content.push_str(&format!("{}={}\n", key, value));
+    }
+    content
+}
+
+/// Write `KEY=value` lines (no `export`/`set`) readable by python-dotenv,
+/// `docker --env-file` and similar tools
+fn write_dotenv(vars: &[(&str, &str)], path: &Path, quote: bool) -> Result<()> {
+    fs::write(path, dotenv_content(vars, quote))
+        .with_context(|| format!("Failed to write {}", path.display()))
 }
 
-/// Env command: print environment variables
-pub fn env_vars() -> Result<()> {
-    let (sdk_root, lib_path, qml_path, plugin_path, mpf_plugin_path, _host_path, host_qml_path) =
-        build_env_paths()?;
+/// Write the variables as a script for the platform shell
+fn write_shell_env(vars: &[(&str, &str)], path: &Path) -> Result<()> {
+    let mut content = String::new();
+    for (key, value) in vars {
+        let line = if cfg!(windows) {
+            if *key == "PATH" {
+                format!("set PATH={};%PATH%\n", value)
+            } else {
+                format!("set {}={}\n", key, value)
+            }
+        } else {
+            format!("export {}=\"{}\"\n", key, value)
+        };
+        content.push_str(&line);
+    }
+    fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Variables exported by `mpf-dev env`, in output order
+fn env_pairs(paths: &EnvPaths, qt_hint: Option<&str>) -> Vec<(&'static str, String)> {
+    let prefix_path = match qt_hint {
+        Some(qt) => format!("{};{}", qt, paths.sdk_root),
+        None => paths.sdk_root.clone(),
+    };
+
+    let mut vars = vec![
+        ("MPF_SDK_ROOT", paths.sdk_root.clone()),
+        ("CMAKE_PREFIX_PATH", prefix_path),
+        ("QML_IMPORT_PATH", paths.qml_path.clone()),
+    ];
+    for var in library_path_vars() {
+        vars.push((var, paths.lib_path.clone()));
+    }
+    vars.push(("QT_PLUGIN_PATH", paths.plugin_path.clone()));
+    if !paths.mpf_plugin_path.is_empty() {
+        vars.push(("MPF_PLUGIN_PATH", paths.mpf_plugin_path.clone()));
+    }
+    if let Some(ref hqp) = paths.host_qml_path {
+        vars.push(("MPF_QML_PATH", hqp.clone()));
+    }
+    vars
+}
+
+/// Env command: print environment variables, or write them to a file
+pub fn env_vars(
+    output: Option<String>,
+    format: Format,
+    force: bool,
+    check: bool,
+    diff: bool,
+) -> Result<()> {
+    let paths = build_env_paths()?;
+
+    if diff {
+        print_env_diff(&paths);
+        return Ok(());
+    }
+
+    if check {
+        eprintln!("{}", "Checking environment paths...".bold());
+        let missing = path_problems(&paths);
+        report_missing_paths(&missing);
+        if !missing.is_empty() {
+            eprintln!("{} Some environment paths do not exist", "✗".red());
+            std::process::exit(1);
+        }
+        eprintln!("{} All environment paths exist", "✓".green());
+    }
+
+    let quote = format == Format::PythonDotenv;
+    if output.is_some() || format != Format::Shell {
+        let qt_hint = detect_qt_path();
+        let pairs = env_pairs(&paths, qt_hint.as_deref());
+        let vars: Vec<(&str, &str)> = pairs.iter().map(|(k, v)| (*k, v.as_str())).collect();
+
+        let path = match output {
+            Some(p) => PathBuf::from(p),
+            None => {
+                print!("{}", dotenv_content(&vars, quote));
+                return Ok(());
+            }
+        };
+
+        if path.exists()
+            && !force
+            && !confirm(&format!("{} already exists. Overwrite?", path.display()))?
+        {
+            println!("{} Aborted", "Note:".yellow());
+            return Ok(());
+        }
+
+        match format {
+            Format::Shell => write_shell_env(&vars, &path)?,
+            Format::Dotenv | Format::PythonDotenv => write_dotenv(&vars, &path, quote)?,
+        }
+        println!("{} Environment written to {}", "✓".green(), path.display());
+        if format == Format::Dotenv {
+            println!(
+                "  {}",
+                format!("docker run --env-file {} <image> ...", path.display()).dimmed()
+            );
+        }
+        return Ok(());
+    }
+
+    let EnvPaths {
+        sdk_root,
+        lib_path,
+        qml_path,
+        plugin_path,
+        mpf_plugin_path,
+        host_qml_path,
+        ..
+    } = paths;
 
     println!("{}", "# MPF Development Environment".bold().cyan());
     println!("{}", "# Add these to your shell or IDE:".dimmed());
@@ -149,7 +650,9 @@ pub fn env_vars() -> Result<()> {
             );
         }
         println!("export QML_IMPORT_PATH=\"{}\"", qml_path);
-        println!("export LD_LIBRARY_PATH=\"{}\"", lib_path);
+        for var in library_path_vars() {
+            println!("export {}=\"{}\"", var, lib_path);
+        }
         println!("export QT_PLUGIN_PATH=\"{}\"", plugin_path);
         if !mpf_plugin_path.is_empty() {
             println!("export MPF_PLUGIN_PATH=\"{}\"", mpf_plugin_path);
@@ -217,27 +720,295 @@ pub fn env_vars() -> Result<()> {
     Ok(())
 }
 
+/// Resolve `run --plugin/--qml/--lib` arguments against the current directory
+fn absolute_paths(paths: &[String]) -> Result<Vec<String>> {
+    paths
+        .iter()
+        .map(|p| {
+            let path = Path::new(p);
+            if !path.is_dir() {
+                bail!("Directory not found: {}", path.display());
+            }
+            let abs = path
+                .canonicalize()
+                .with_context(|| format!("Failed to resolve {}", path.display()))?;
+            Ok(super::normalize_path(abs))
+        })
+        .collect()
+}
+
+/// Put `extra` in front of a separator-joined path list, dropping duplicates
+fn prepend_paths(extra: &[String], joined: &str) -> String {
+    let sep = if cfg!(windows) { ';' } else { ':' };
+    let mut parts: Vec<&str> = extra.iter().map(String::as_str).collect();
+    for part in joined.split(sep).filter(|p| !p.is_empty()) {
+        if !parts.contains(&part) {
+            parts.push(part);
+        }
+    }
+    parts.join(&sep.to_string())
+}
+
+/// Variables kept by `run --isolated`: what a Qt application needs to reach
+/// the display/session and find its temp and profile directories
+const ISOLATED_PASSTHROUGH: &[&str] = &[
+    "DISPLAY",
+    "WAYLAND_DISPLAY",
+    "XDG_RUNTIME_DIR",
+    "XDG_SESSION_TYPE",
+    "XAUTHORITY",
+    "DBUS_SESSION_BUS_ADDRESS",
+    "HOME",
+    "USER",
+    "LANG",
+    "TMPDIR",
+    "TEMP",
+    "TMP",
+    "SYSTEMROOT",
+    "SYSTEMDRIVE",
+    "WINDIR",
+    "USERPROFILE",
+    "APPDATA",
+    "LOCALAPPDATA",
+    "COMSPEC",
+];
+
+/// Parse a simple env file: `KEY=VALUE` per line, `#` comments, optional `export ` prefix.
+/// Surrounding single or double quotes on the value are stripped.
+fn parse_env_file(path: &Path) -> Result<Vec<(String, String)>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read env file {}", path.display()))?;
+
+    let mut vars = Vec::new();
+    for (lineno, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line).trim_start();
+        let (key, value) = match line.split_once('=') {
+            Some(kv) => kv,
+            None => bail!(
+                "{}:{}: expected KEY=VALUE, got '{}'",
+                path.display(),
+                lineno + 1,
+                line
+            ),
+        };
+        let key = key.trim();
+        if key.is_empty() {
+            bail!("{}:{}: empty variable name", path.display(), lineno + 1);
+        }
+        let value = value.trim();
+        let value = if value.len() >= 2
+            && ((value.starts_with('"') && value.ends_with('"'))
+                || (value.starts_with('\'') && value.ends_with('\'')))
+        {
+            &value[1..value.len() - 1]
+        } else {
+            value
+        };
+        vars.push((key.to_string(), value.to_string()));
+    }
+    Ok(vars)
+}
+
+/// Quote a value for the current platform's shell if it needs quoting
+fn shell_quote(value: &str) -> String {
+    if cfg!(windows) {
+        if value.is_empty() || value.contains([' ', '\t', '&', '|', '<', '>', '^']) {
+            format!("\"{}\"", value)
+        } else {
+            value.to_string()
+        }
+    } else if !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c))
+    {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+/// Format a Command as shell-eval-able lines: environment variables that
+/// differ from the ambient environment, followed by the command line.
+fn format_command_debug(cmd: &Command) -> String {
+    let mut lines = Vec::new();
+
+    let mut envs: Vec<_> = cmd.get_envs().collect();
+    envs.sort_by(|a, b| a.0.cmp(b.0));
+    for (key, value) in envs {
+        let key = key.to_string_lossy();
+        match value {
+            Some(value) => {
+                if std::env::var_os(key.as_ref()).as_deref() == Some(value) {
+                    continue;
+                }
+                let value = value.to_string_lossy();
+                if cfg!(windows) {
+                    lines.push(format!("set \"{}={}\"", key, value));
+                } else {
+                    lines.push(format!("export {}={}", key, shell_quote(&value)));
+                }
+            }
+            None => {
+                if cfg!(windows) {
+                    lines.push(format!("set {}=", key));
+                } else {
+                    lines.push(format!("unset {}", key));
+                }
+            }
+        }
+    }
+
+    let mut command_line = vec![shell_quote(&cmd.get_program().to_string_lossy())];
+    command_line.extend(cmd.get_args().map(|a| shell_quote(&a.to_string_lossy())));
+    lines.push(command_line.join(" "));
+
+    lines.join("\n")
+}
+
+/// Whether a graphical display is available (X11 or Wayland)
+fn has_display() -> bool {
+    std::env::var_os("DISPLAY").is_some() || std::env::var_os("WAYLAND_DISPLAY").is_some()
+}
+
+/// Read the last `n` lines of a text file
+pub(super) fn tail_file(path: &Path, n: usize) -> Result<Vec<String>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let lines: Vec<&str> = content.lines().collect();
+    let start = lines.len().saturating_sub(n);
+    Ok(lines[start..].iter().map(|l| l.to_string()).collect())
+}
+
+/// Options for the `run` command
+pub struct RunOptions {
+    /// Print the environment before launching
+    pub debug: bool,
+    /// Extra KEY=VALUE file loaded before the MPF variables
+    pub env_file: Option<String>,
+    /// Print the launch command instead of executing it
+    pub dry_run: bool,
+    /// Kill mpf-host after this many seconds
+    pub timeout: Option<u64>,
+    /// Redirect mpf-host stdout/stderr to this file
+    pub capture: Option<String>,
+    /// Number of captured lines to echo after exit
+    pub tail: usize,
+    /// Qt platform plugin (QT_QPA_PLATFORM)
+    pub platform: Option<String>,
+    /// Clear the environment except for the variables in ISOLATED_PASSTHROUGH
+    pub isolated: bool,
+    /// One-shot MPF_PLUGIN_PATH entries (not saved to dev.json)
+    pub extra_plugins: Vec<String>,
+    /// One-shot QML_IMPORT_PATH entries
+    pub extra_qml: Vec<String>,
+    /// One-shot library path entries
+    pub extra_libs: Vec<String>,
+    /// Arguments passed through to mpf-host
+    pub args: Vec<String>,
+}
+
 /// Run command: execute mpf-host with development overrides
-pub fn run(debug: bool, args: Vec<String>) -> Result<()> {
+pub async fn run(opts: RunOptions) -> Result<()> {
+    let RunOptions {
+        debug,
+        env_file,
+        dry_run,
+        timeout,
+        capture,
+        tail,
+        platform,
+        isolated,
+        extra_plugins,
+        extra_qml,
+        extra_libs,
+        args,
+    } = opts;
+
     let current = config::current_link();
     if !current.exists() {
         bail!("No SDK version set. Run `mpf-dev setup` first.");
     }
 
-    let (sdk_root, lib_path, qml_path, plugin_path, mpf_plugin_path, host_path, host_qml_path) =
-        build_env_paths()?;
+    // The active environment's env file loads first so --env-file can override it
+    let mut file_vars = Vec::new();
+    if let Some(name) = DevConfig::load()?.active_environment {
+        let path = config::environment_env_file(&name);
+        if path.is_file() {
+            file_vars.extend(parse_env_file(&path)?);
+        }
+    }
+
+    // Validate and parse the env file up front so we never launch half-configured
+    if let Some(path) = env_file.as_deref() {
+        let path = Path::new(path);
+        if !path.is_file() {
+            bail!("Env file not found: {}", path.display());
+        }
+        file_vars.extend(parse_env_file(path)?);
+    }
+
+    let paths = build_env_paths()?;
+    if debug {
+        let missing = path_problems(&paths);
+        if !missing.is_empty() {
+            eprintln!("{} Some environment paths do not exist:", "Warning:".yellow());
+            report_missing_paths(&missing);
+        }
+    }
+
+    let EnvPaths {
+        sdk_root,
+        lib_path,
+        qml_path,
+        plugin_path,
+        mpf_plugin_path,
+        host_path,
+        host_qml_path,
+    } = paths;
+
+    // One-shot overrides from --plugin/--qml/--lib go first; dev.json is untouched
+    let extra_plugins = absolute_paths(&extra_plugins)?;
+    let extra_qml = absolute_paths(&extra_qml)?;
+    let extra_libs = absolute_paths(&extra_libs)?;
+    let mpf_plugin_path = prepend_paths(&extra_plugins, &mpf_plugin_path);
+    let qml_path = prepend_paths(&extra_qml, &qml_path);
+    let lib_path = prepend_paths(&extra_libs, &lib_path);
 
     if !host_path.exists() {
-        bail!("mpf-host not found at: {}", host_path.display());
+        if !dry_run {
+            bail!("mpf-host not found at: {}", host_path.display());
+        }
+        eprintln!(
+            "{} mpf-host not found at: {}",
+            "Warning:".yellow(),
+            host_path.display()
+        );
     }
 
     if debug {
+        let overrides = [
+            ("--plugin", &extra_plugins),
+            ("--qml", &extra_qml),
+            ("--lib", &extra_libs),
+        ];
+        if overrides.iter().any(|(_, paths)| !paths.is_empty()) {
+            println!("{}", "One-shot overrides (not saved):".dimmed());
+            for (flag, paths) in overrides {
+                for path in paths {
+                    println!("  {} {}", flag, path);
+                }
+            }
+        }
         println!("{}", "Running with development overrides:".dimmed());
         println!("  MPF_SDK_ROOT={}", sdk_root);
-        #[cfg(unix)]
-        println!("  LD_LIBRARY_PATH={}", lib_path);
-        #[cfg(windows)]
-        println!("  PATH={}", lib_path);
+        for var in library_path_vars() {
+            println!("  {}={}", var, lib_path);
+        }
         println!("  QML_IMPORT_PATH={}", qml_path);
         println!("  QT_PLUGIN_PATH={}", plugin_path);
         if !mpf_plugin_path.is_empty() {
@@ -252,17 +1023,48 @@ pub fn run(debug: bool, args: Vec<String>) -> Result<()> {
     let mut cmd = Command::new(&host_path);
     cmd.args(&args);
 
+    // Isolated runs see only the session basics plus what mpf-dev sets
+    if isolated {
+        cmd.env_clear();
+        for name in ISOLATED_PASSTHROUGH {
+            if let Some(value) = std::env::var_os(name) {
+                cmd.env(name, value);
+            }
+        }
+        #[cfg(unix)]
+        cmd.env("PATH", "/usr/local/bin:/usr/bin:/bin");
+    }
+
+    // Env file variables go first so the MPF-specific variables below override them
+    for (key, value) in &file_vars {
+        cmd.env(key, value);
+    }
+    if debug && !file_vars.is_empty() {
+        println!(
+            "{} Loaded {} variable(s) from env file",
+            "->".cyan(),
+            file_vars.len()
+        );
+    }
+
     // MPF_SDK_ROOT tells mpf-host where the SDK is installed
     cmd.env("MPF_SDK_ROOT", &sdk_root);
 
     #[cfg(unix)]
     {
-        cmd.env("LD_LIBRARY_PATH", &lib_path);
+        for var in library_path_vars() {
+            cmd.env(var, &lib_path);
+        }
     }
 
     #[cfg(windows)]
     {
-        let current_path = env::var("PATH").unwrap_or_default();
+        let current_path = if isolated {
+            let root = std::env::var("SYSTEMROOT").unwrap_or_else(|_| r"C:\Windows".to_string());
+            format!(r"{0}\System32;{0}", root)
+        } else {
+            std::env::var("PATH").unwrap_or_default()
+        };
         cmd.env("PATH", format!("{};{}", lib_path, current_path));
     }
 
@@ -279,7 +1081,108 @@ pub fn run(debug: bool, args: Vec<String>) -> Result<()> {
         cmd.env("MPF_QML_PATH", hqp);
     }
 
-    let status = cmd.status()?;
+    // Qt platform plugin: explicit flag, or offscreen when there is no display
+    let platform = platform.or_else(|| {
+        let headless = cfg!(target_os = "linux")
+            && !has_display()
+            && (isolated || std::env::var_os("QT_QPA_PLATFORM").is_none());
+        if headless {
+            eprintln!(
+                "{} No DISPLAY or WAYLAND_DISPLAY set, using QT_QPA_PLATFORM=offscreen",
+                "Warning:".yellow()
+            );
+            Some("offscreen".to_string())
+        } else {
+            None
+        }
+    });
+    if let Some(ref platform) = platform {
+        cmd.env("QT_QPA_PLATFORM", platform);
+        if debug && platform == "offscreen" {
+            // No window to look at, so surface as much logging as possible
+            cmd.env("QT_LOGGING_RULES", "*.debug=true");
+        }
+    }
+
+    if debug && isolated {
+        let mut vars: Vec<(String, String)> = cmd
+            .get_envs()
+            .filter_map(|(k, v)| {
+                Some((k.to_string_lossy().to_string(), v?.to_string_lossy().to_string()))
+            })
+            .collect();
+        vars.sort();
+        println!("{}", "Isolated environment:".dimmed());
+        for (key, value) in &vars {
+            println!("  {}={}", key, value);
+        }
+        println!();
+    }
+
+    if dry_run {
+        if isolated {
+            let kept: Vec<&str> = ISOLATED_PASSTHROUGH
+                .iter()
+                .copied()
+                .filter(|name| std::env::var_os(name).is_some())
+                .collect();
+            let comment = if cfg!(windows) { "rem" } else { "#" };
+            println!(
+                "{} --isolated: empty environment except {}",
+                comment,
+                kept.join(", ")
+            );
+        }
+        println!("{}", format_command_debug(&cmd));
+        return Ok(());
+    }
+
+    // Redirect both stdout and stderr into the capture file
+    let capture_path = capture.as_ref().map(PathBuf::from);
+    if let Some(ref path) = capture_path {
+        let mut file = File::create(path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+        writeln!(
+            file,
+            "# mpf-host output captured by mpf-dev at {}",
+            chrono::Local::now().to_rfc3339()
+        )?;
+        cmd.stdout(Stdio::from(file.try_clone()?));
+        cmd.stderr(Stdio::from(file));
+        println!("{} Capturing output to {}", "->".cyan(), path.display());
+    }
+
+    let code = match timeout {
+        Some(secs) => {
+            let mut child = tokio::process::Command::from(cmd).spawn()?;
+            match tokio::time::timeout(Duration::from_secs(secs), child.wait()).await {
+                Ok(status) => status?.code().unwrap_or(1),
+                Err(_) => {
+                    let _ = child.kill().await;
+                    eprintln!(
+                        "{} mpf-host did not exit within {}s, killed",
+                        "Error:".red(),
+                        secs
+                    );
+                    124
+                }
+            }
+        }
+        None => cmd.status()?.code().unwrap_or(1),
+    };
+
+    if let Some(ref path) = capture_path {
+        if tail > 0 {
+            println!();
+            println!(
+                "{}",
+                format!("── last {} lines of {} ──", tail, path.display()).dimmed()
+            );
+            for line in tail_file(path, tail)? {
+                println!("{}", line);
+            }
+        }
+    }
 
-    std::process::exit(status.code().unwrap_or(1));
+    std::process::exit(code);
 }
@@ -1,32 +1,94 @@
 use anyhow::{bail, Context, Result};
 use colored::*;
 use futures_util::StreamExt;
+use serde::Serialize;
 use indicatif::{ProgressBar, ProgressStyle};
 use std::fs::{self, File};
-use std::io::Write;
-use std::process::Command;
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use std::time::Duration;
 
 use crate::config::{self, DevConfig};
 
+use super::cache;
 use super::GITHUB_REPO;
 
+/// Options for `setup`
+pub struct SetupOptions {
+    /// Version or alias to install (default: latest stable)
+    pub version: Option<String>,
+    /// Install the latest prerelease instead
+    pub prerelease: bool,
+    /// Target architecture (default: this machine's)
+    pub arch: Option<String>,
+    /// GitHub token for this run (overrides dev.json and GITHUB_TOKEN)
+    pub token: Option<String>,
+    /// Exact release asset to download instead of `mpf-<os>-<arch>`
+    pub asset: Option<String>,
+    /// Only list the release's assets
+    pub list_assets: bool,
+    /// Extra download attempts after a network failure
+    pub retries: u32,
+}
+
 /// Setup command: download and install SDK
-pub async fn setup(version: Option<String>) -> Result<()> {
+pub async fn setup(opts: SetupOptions) -> Result<()> {
     println!("{}", "MPF SDK Setup".bold().cyan());
 
-    let version = match version {
-        Some(v) => v,
-        None => {
+    let SetupOptions {
+        version,
+        prerelease,
+        arch,
+        token,
+        asset,
+        list_assets,
+        retries,
+    } = opts;
+    let mut dev_config = DevConfig::load().unwrap_or_default();
+    let token = token.or_else(|| github_token(&dev_config));
+    let token = token.as_deref();
+
+    // `stable` and `nightly` always mean the newest release of that kind on
+    // GitHub; other aliases resolve to the version they were set to
+    let requested = match version.as_deref() {
+        _ if prerelease => Some("nightly"),
+        None => Some("stable"),
+        Some(v) if v == "stable" || v == "nightly" => Some(v),
+        Some(_) => None,
+    };
+    let version = match requested {
+        Some("nightly") => {
+            println!("Fetching latest prerelease...");
+            let v = fetch_latest_prerelease(token).await?;
+            dev_config
+                .version_aliases
+                .insert("nightly".to_string(), normalize_version(&v));
+            v
+        }
+        Some(_) => {
             println!("Fetching latest release...");
-            fetch_latest_version().await?
+            let v = fetch_latest_version(token).await?;
+            dev_config
+                .version_aliases
+                .insert("stable".to_string(), normalize_version(&v));
+            v
+        }
+        None => {
+            let v = version.unwrap_or_default();
+            dev_config.version_aliases.get(&v).cloned().unwrap_or(v)
         }
     };
 
-    let version_normalized = if version.starts_with('v') {
-        version.clone()
-    } else {
-        format!("v{}", version)
-    };
+    let version_normalized = normalize_version(&version);
+
+    if list_assets {
+        let assets = list_release_assets(&version_normalized, token).await?;
+        println!("{} {}:", "Assets of".bold(), version_normalized.green());
+        for (name, size) in &assets {
+            println!("  {} ({})", name, cache::format_size(*size));
+        }
+        return Ok(());
+    }
 
     println!("Installing SDK version: {}", version_normalized.green());
 
@@ -42,14 +104,16 @@ pub async fn setup(version: Option<String>) -> Result<()> {
         );
     } else {
         // Download and extract
-        download_and_extract(&version_normalized, &version_dir).await?;
+        let asset_name = asset
+            .unwrap_or_else(|| asset_name(&arch.unwrap_or_else(|| host_arch().to_string())));
+        download_and_extract(&version_normalized, &version_dir, &asset_name, token, retries)
+            .await?;
     }
 
     // Set as current
     config::set_current_version(&version_normalized)?;
 
     // Update dev.json
-    let mut dev_config = DevConfig::load().unwrap_or_default();
     dev_config.sdk_version = Some(version_normalized.clone());
     dev_config.save()?;
 
@@ -63,20 +127,96 @@ pub async fn setup(version: Option<String>) -> Result<()> {
     Ok(())
 }
 
-async fn fetch_latest_version() -> Result<String> {
-    let url = format!(
-        "https://api.github.com/repos/{}/releases/latest",
-        GITHUB_REPO
-    );
+/// Normalize a version string to the "v"-prefixed directory name
+pub(super) fn normalize_version(version: &str) -> String {
+    if version.starts_with('v') {
+        version.to_string()
+    } else {
+        format!("v{}", version)
+    }
+}
+
+/// Resolve a version argument to an installed version name, or bail.
+/// Any version directory counts, so incomplete installs can still be
+/// verified and removed.
+fn require_installed(version: &str) -> Result<String> {
+    let version_normalized = normalize_version(version);
+    if !config::version_dir(&version_normalized).is_dir() {
+        bail!(
+            "Version {} is not installed. Run `mpf-dev versions` to list installed versions.",
+            version_normalized
+        );
+    }
+    Ok(version_normalized)
+}
+
+/// GitHub token from dev.json, falling back to the GITHUB_TOKEN environment variable
+pub(super) fn github_token(dev_config: &DevConfig) -> Option<String> {
+    dev_config
+        .github_token
+        .clone()
+        .or_else(|| std::env::var("GITHUB_TOKEN").ok())
+        .filter(|t| !t.is_empty())
+}
+
+/// GET request to GitHub, authenticated when a token is available
+pub(super) fn github_get(
+    client: &reqwest::Client,
+    url: &str,
+    token: Option<&str>,
+) -> reqwest::RequestBuilder {
+    let request = client.get(url).header("User-Agent", "mpf-dev");
+    match token {
+        Some(token) => request.bearer_auth(token),
+        None => request,
+    }
+}
+
+/// Request for a release asset. With a token the API endpoint is used, which
+/// also works for private repositories.
+pub(super) fn asset_request(
+    client: &reqwest::Client,
+    asset: &serde_json::Value,
+    token: Option<&str>,
+) -> Option<reqwest::RequestBuilder> {
+    match token {
+        Some(_) => asset["url"].as_str().map(|url| {
+            github_get(client, url, token).header("Accept", "application/octet-stream")
+        }),
+        None => asset["browser_download_url"]
+            .as_str()
+            .map(|url| github_get(client, url, None)),
+    }
+}
+
+/// Fetch release JSON from the GitHub API (`None` = latest release)
+pub(super) async fn fetch_release(
+    tag: Option<&str>,
+    token: Option<&str>,
+) -> Result<serde_json::Value> {
+    let url = match tag {
+        Some(tag) => format!(
+            "https://api.github.com/repos/{}/releases/tags/{}",
+            GITHUB_REPO, tag
+        ),
+        None => format!(
+            "https://api.github.com/repos/{}/releases/latest",
+            GITHUB_REPO
+        ),
+    };
 
     let client = reqwest::Client::new();
-    let resp = client
-        .get(&url)
-        .header("User-Agent", "mpf-dev")
-        .send()
-        .await?
-        .json::<serde_json::Value>()
-        .await?;
+    let resp = github_get(&client, &url, token).send().await?;
+
+    if !resp.status().is_success() {
+        bail!("GitHub API request failed: {} ({})", resp.status(), url);
+    }
+
+    Ok(resp.json::<serde_json::Value>().await?)
+}
+
+async fn fetch_latest_version(token: Option<&str>) -> Result<String> {
+    let resp = fetch_release(None, token).await?;
 
     resp["tag_name"]
         .as_str()
@@ -84,38 +224,164 @@ async fn fetch_latest_version() -> Result<String> {
         .context("Could not find latest release")
 }
 
-async fn download_and_extract(version: &str, dest: &std::path::PathBuf) -> Result<()> {
-    // Determine platform and asset name
-    let (asset_name, is_tarball) = if cfg!(target_os = "windows") {
-        ("mpf-windows-x64.zip".to_string(), false)
+/// Tag of the newest prerelease (the `latest` endpoint skips prereleases)
+async fn fetch_latest_prerelease(token: Option<&str>) -> Result<String> {
+    let url = format!(
+        "https://api.github.com/repos/{}/releases?per_page=30",
+        GITHUB_REPO
+    );
+    let client = reqwest::Client::new();
+    let resp = github_get(&client, &url, token).send().await?;
+
+    if !resp.status().is_success() {
+        bail!("GitHub API request failed: {} ({})", resp.status(), url);
+    }
+
+    let releases = resp.json::<Vec<serde_json::Value>>().await?;
+    releases
+        .iter()
+        .find(|r| r["prerelease"].as_bool() == Some(true))
+        .and_then(|r| r["tag_name"].as_str())
+        .map(|s| s.to_string())
+        .context("No prerelease found")
+}
+
+/// Architectures accepted by `setup --arch`
+pub const SDK_ARCHES: &[&str] = &["x64", "arm64", "x86"];
+
+/// This machine's architecture in `setup --arch` terms
+fn host_arch() -> &'static str {
+    match std::env::consts::ARCH {
+        "aarch64" => "arm64",
+        "x86" => "x86",
+        _ => "x64",
+    }
+}
+
+/// How a release asset is unpacked, from its file name
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ArchiveKind {
+    Zip,
+    TarGz,
+    Dmg,
+}
+
+impl ArchiveKind {
+    fn of(asset_name: &str) -> Self {
+        if asset_name.ends_with(".zip") {
+            ArchiveKind::Zip
+        } else if asset_name.ends_with(".dmg") {
+            ArchiveKind::Dmg
+        } else {
+            ArchiveKind::TarGz
+        }
+    }
+}
+
+/// Release asset for this OS and the given architecture
+fn asset_name(arch: &str) -> String {
+    let os = if cfg!(target_os = "windows") {
+        "windows"
+    } else if cfg!(target_os = "macos") {
+        "macos"
     } else {
-        ("mpf-linux-x64.tar.gz".to_string(), true)
+        "linux"
     };
+    // Release assets follow each platform's own name for 64-bit ARM
+    let arch = match (os, arch) {
+        ("windows", "arm64") => "arm64",
+        (_, "arm64") => "aarch64",
+        (_, other) => other,
+    };
+    if os == "windows" {
+        format!("mpf-{}-{}.zip", os, arch)
+    } else {
+        format!("mpf-{}-{}.tar.gz", os, arch)
+    }
+}
 
-    let download_url = format!(
-        "https://github.com/{}/releases/download/{}/{}",
-        GITHUB_REPO, version, asset_name
-    );
+/// Disk image published in place of a macOS tarball. Disk images use
+/// Apple's `arm64` name where the tarballs use `aarch64`.
+fn dmg_asset_name(tarball: &str) -> Option<String> {
+    let stem = tarball.strip_suffix(".tar.gz")?;
+    let stem = match stem.strip_suffix("-aarch64") {
+        Some(base) => format!("{}-arm64", base),
+        None => stem.to_string(),
+    };
+    Some(format!("{}.dmg", stem))
+}
 
-    println!("Downloading {} ({})...", asset_name, version);
+/// Names and sizes of a release's assets
+async fn list_release_assets(version: &str, token: Option<&str>) -> Result<Vec<(String, u64)>> {
+    let release = fetch_release(Some(version), token).await?;
+    let assets = release["assets"]
+        .as_array()
+        .with_context(|| format!("Release {} has no assets", version))?;
+    Ok(assets
+        .iter()
+        .filter_map(|a| {
+            let name = a["name"].as_str()?;
+            Some((name.to_string(), a["size"].as_u64().unwrap_or(0)))
+        })
+        .collect())
+}
 
-    let client = reqwest::Client::new();
-    let resp = client
-        .get(&download_url)
-        .header("User-Agent", "mpf-dev")
-        .send()
-        .await?;
+/// Non-success HTTP response while downloading
+#[derive(Debug)]
+struct HttpStatusError {
+    status: reqwest::StatusCode,
+    url: String,
+}
+
+impl std::fmt::Display for HttpStatusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to download SDK: {} ({})", self.status, self.url)
+    }
+}
+
+impl std::error::Error for HttpStatusError {}
+
+/// Network errors, server errors and rate limiting are worth retrying;
+/// other HTTP errors (e.g. 404) and local I/O errors are not
+fn is_retryable(err: &anyhow::Error) -> bool {
+    if err.downcast_ref::<reqwest::Error>().is_some() {
+        return true;
+    }
+    err.downcast_ref::<HttpStatusError>()
+        .map(|e| e.status.is_server_error() || e.status == reqwest::StatusCode::TOO_MANY_REQUESTS)
+        .unwrap_or(false)
+}
+
+/// Download an archive into the cache, unless a verified copy is already there
+async fn download_archive(
+    request: reqwest::RequestBuilder,
+    version: &str,
+    asset_name: &str,
+    archive_path: &Path,
+) -> Result<()> {
+    let resp = request.send().await?;
 
     if !resp.status().is_success() {
-        bail!(
-            "Failed to download SDK: {} ({})",
-            resp.status(),
-            download_url
-        );
+        return Err(HttpStatusError {
+            status: resp.status(),
+            url: resp.url().to_string(),
+        }
+        .into());
     }
 
     let total_size = resp.content_length().unwrap_or(0);
 
+    if cache::is_cached(version, asset_name, total_size) {
+        // Cache hit: drop the response without reading the body
+        drop(resp);
+        println!(
+            "{} Using cached archive {}",
+            "✓".green(),
+            archive_path.display()
+        );
+        return Ok(());
+    }
+
     let pb = ProgressBar::new(total_size);
     pb.set_style(
         ProgressStyle::default_bar()
@@ -123,9 +389,10 @@ async fn download_and_extract(version: &str, dest: &std::path::PathBuf) -> Resul
             .progress_chars("#>-"),
     );
 
-    // Download to temp file
-    let temp_ext = if is_tarball { "tar.gz.tmp" } else { "zip.tmp" };
-    let temp_path = dest.with_extension(temp_ext);
+    // Download to a partial file so an interrupted download is never
+    // mistaken for a complete cached archive. File::create truncates any
+    // leftover from a failed attempt.
+    let temp_path = archive_path.with_extension("part");
     if let Some(parent) = temp_path.parent() {
         fs::create_dir_all(parent)?;
     }
@@ -140,77 +407,373 @@ async fn download_and_extract(version: &str, dest: &std::path::PathBuf) -> Resul
         downloaded += chunk.len() as u64;
         pb.set_position(downloaded);
     }
+    drop(file);
 
     pb.finish_with_message("Downloaded");
 
+    fs::rename(&temp_path, archive_path)?;
+    cache::record(version, asset_name, archive_path)?;
+    Ok(())
+}
+
+async fn download_and_extract(
+    version: &str,
+    dest: &std::path::PathBuf,
+    asset_name: &str,
+    token: Option<&str>,
+    retries: u32,
+) -> Result<()> {
+    let client = reqwest::Client::new();
+    let mut asset = None;
+    let mut asset_name = asset_name.to_string();
+
+    // Check the release actually ships this asset (skipped if the API is unavailable)
+    if let Ok(release) = fetch_release(Some(version), token).await {
+        let assets = release["assets"].as_array().cloned().unwrap_or_default();
+        let names: Vec<&str> = assets.iter().filter_map(|x| x["name"].as_str()).collect();
+        // Mac SDKs may ship only as a disk image
+        if cfg!(target_os = "macos") && !names.contains(&asset_name.as_str()) {
+            if let Some(dmg) = dmg_asset_name(&asset_name) {
+                if names.contains(&dmg.as_str()) {
+                    println!("{} Using {} ({} not published)", "->".cyan(), dmg, asset_name);
+                    asset_name = dmg;
+                }
+            }
+        }
+        if !names.is_empty() && !names.contains(&asset_name.as_str()) {
+            bail!(
+                "Release {} has no asset {}. Available assets: {}",
+                version,
+                asset_name,
+                names.join(", ")
+            );
+        }
+        asset = assets
+            .into_iter()
+            .find(|a| a["name"].as_str() == Some(asset_name.as_str()));
+    }
+    let asset_name = asset_name.as_str();
+
+    let download_url = format!(
+        "https://github.com/{}/releases/download/{}/{}",
+        GITHUB_REPO, version, asset_name
+    );
+    let archive_path = cache::archive_path(version, asset_name);
+
+    println!("Downloading {} ({})...", asset_name, version);
+
+    let attempts = retries + 1;
+    let mut attempt = 1;
+    loop {
+        let request = asset
+            .as_ref()
+            .and_then(|a| asset_request(&client, a, token))
+            .unwrap_or_else(|| github_get(&client, &download_url, token));
+        match download_archive(request, version, asset_name, &archive_path).await {
+            Ok(()) => break,
+            Err(e) if attempt < attempts && is_retryable(&e) => {
+                println!("{} {}", "Warning:".yellow(), e);
+                // 1s, 4s, 16s, ...
+                tokio::time::sleep(Duration::from_secs(4u64.pow(attempt - 1))).await;
+                attempt += 1;
+                println!("{} Retrying (attempt {}/{})...", "->".cyan(), attempt, attempts);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
     // Extract
     println!("Extracting...");
     fs::create_dir_all(dest)?;
 
-    if is_tarball {
-        // Extract tar.gz using tar command (more reliable on Unix)
-        let status = Command::new("tar")
-            .args([
-                "-xzf",
-                &temp_path.to_string_lossy(),
-                "-C",
-                &dest.to_string_lossy(),
-            ])
-            .status()
-            .context("Failed to run tar command")?;
-        if !status.success() {
-            bail!("tar extraction failed");
+    match ArchiveKind::of(asset_name) {
+        ArchiveKind::TarGz => extract_tarball(&archive_path, dest)?,
+        ArchiveKind::Zip => {
+            let file = File::open(&archive_path)?;
+            let mut archive = zip::ZipArchive::new(file)?;
+            archive.extract(dest)?;
+        }
+        ArchiveKind::Dmg => {
+            if !cfg!(target_os = "macos") {
+                bail!("{} is a macOS disk image and can only be installed on macOS", asset_name);
+            }
+            extract_dmg(&archive_path, dest).await?;
         }
-    } else {
-        // Extract zip
-        let file = File::open(&temp_path)?;
-        let mut archive = zip::ZipArchive::new(file)?;
-        archive.extract(dest)?;
     }
 
-    // Clean up temp file
-    fs::remove_file(&temp_path)?;
-
     println!("{} Extraction complete", "✓".green());
     Ok(())
 }
 
+/// Unpack a .tar.gz archive into `dest` (no system `tar` needed)
+fn extract_tarball(src: &Path, dest: &Path) -> Result<()> {
+    let file = File::open(src).with_context(|| format!("Failed to open {}", src.display()))?;
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(file));
+    archive.set_preserve_permissions(true);
+    archive
+        .unpack(dest)
+        .with_context(|| format!("Failed to extract {}", src.display()))
+}
+
+/// Where `extract_dmg` mounts the disk image
+const DMG_MOUNT_POINT: &str = "/tmp/mpf-mount";
+
+/// Copy the contents of a macOS disk image into `dest` (mount, copy, detach)
+async fn extract_dmg(dmg_path: &Path, dest: &Path) -> Result<()> {
+    let output = tokio::process::Command::new("hdiutil")
+        .args(["attach", "-nobrowse", "-readonly", "-mountpoint", DMG_MOUNT_POINT])
+        .arg(dmg_path)
+        .output()
+        .await
+        .context("Failed to run hdiutil")?;
+    if !output.status.success() {
+        bail!(
+            "Failed to mount {}: {}\n\
+             If macOS blocked the image (unverified developer), allow it in\n\
+             System Settings > Privacy & Security, or clear the quarantine flag:\n\
+             \x20 xattr -d com.apple.quarantine {}",
+            dmg_path.display(),
+            String::from_utf8_lossy(&output.stderr).trim(),
+            dmg_path.display()
+        );
+    }
+
+    // Skip the volume's own metadata (.fseventsd, .Trashes, ...)
+    let copied = fs::read_dir(DMG_MOUNT_POINT)
+        .with_context(|| format!("Failed to read {}", DMG_MOUNT_POINT))
+        .and_then(|entries| {
+            for entry in entries.flatten() {
+                if entry.file_name().to_string_lossy().starts_with('.') {
+                    continue;
+                }
+                let target = dest.join(entry.file_name());
+                if entry.path().is_dir() {
+                    copy_dir_all(&entry.path(), &target)?;
+                } else {
+                    fs::copy(entry.path(), &target)?;
+                }
+            }
+            Ok(())
+        });
+
+    // Detach even when the copy failed, so the next attempt can mount again
+    let detached = tokio::process::Command::new("hdiutil")
+        .args(["detach", DMG_MOUNT_POINT])
+        .stdout(std::process::Stdio::null())
+        .status()
+        .await;
+    if !detached.is_ok_and(|s| s.success()) {
+        println!(
+            "{} Could not detach {} (run: hdiutil detach {})",
+            "Warning:".yellow(),
+            DMG_MOUNT_POINT,
+            DMG_MOUNT_POINT
+        );
+    }
+    copied
+}
+
+/// Recursively copy `src` into `dest`, keeping symlinks as links
+fn copy_dir_all(src: &Path, dest: &Path) -> Result<()> {
+    fs::create_dir_all(dest)?;
+    for entry in fs::read_dir(src).with_context(|| format!("Failed to read {}", src.display()))? {
+        let entry = entry?;
+        let target = dest.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            copy_dir_all(&entry.path(), &target)?;
+        } else if file_type.is_symlink() {
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(fs::read_link(entry.path())?, &target)?;
+            #[cfg(windows)]
+            fs::copy(entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)
+                .with_context(|| format!("Failed to copy {}", entry.path().display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Machine-readable `versions --json` output
+#[derive(Serialize)]
+struct VersionsOutput {
+    installed: Vec<String>,
+    latest: Option<String>,
+    outdated: bool,
+}
+
 /// Versions command: list installed versions
-pub fn versions() -> Result<()> {
+pub async fn versions(outdated: bool, json: bool) -> Result<()> {
     let versions = config::installed_versions();
     let current = config::current_version();
 
+    // Newest release on GitHub, when asked to check
+    let latest = if outdated {
+        let token = github_token(&DevConfig::load().unwrap_or_default());
+        Some(fetch_latest_version(token.as_deref()).await?)
+    } else {
+        None
+    };
+    let is_outdated = latest.as_deref().and_then(config::parse_semver).is_some_and(|latest| {
+        versions
+            .iter()
+            .filter_map(|v| config::parse_semver(v))
+            .max()
+            .is_none_or(|newest| latest > newest)
+    });
+
+    if json {
+        let output = VersionsOutput {
+            installed: versions,
+            latest: latest.map(|v| normalize_version(&v)),
+            outdated: is_outdated,
+        };
+        serde_json::to_writer_pretty(std::io::stdout(), &output)?;
+        println!();
+        return Ok(());
+    }
+
     if versions.is_empty() {
         println!("No SDK versions installed.");
         println!("Run {} to install.", "mpf-dev setup".cyan());
         return Ok(());
     }
 
+    let dev_config = DevConfig::load().unwrap_or_default();
+    let aliases_of = |v: &str| {
+        let mut names: Vec<&str> = dev_config
+            .version_aliases
+            .iter()
+            .filter(|(_, target)| target.as_str() == v)
+            .map(|(name, _)| name.as_str())
+            .collect();
+        names.sort();
+        if names.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", names.join(", "))
+        }
+    };
+
     println!("{}", "Installed SDK versions:".bold());
     for v in &versions {
         if Some(v) == current.as_ref() {
-            println!("  {} {} {}", "*".green(), v.green(), "(current)".dimmed());
+            println!(
+                "  {} {} {}{}",
+                "*".green(),
+                v.green(),
+                "(current)".dimmed(),
+                aliases_of(v).cyan()
+            );
+        } else {
+            println!("    {}{}", v, aliases_of(v).cyan());
+        }
+    }
+
+    if let Some(latest) = latest {
+        println!();
+        if is_outdated {
+            println!("{} {} available", "⬆".yellow(), normalize_version(&latest).yellow());
         } else {
-            println!("    {}", v);
+            println!("{} up to date", "✓".green());
         }
     }
 
     Ok(())
 }
 
+/// Alias command: name an SDK version for `setup` and `use`
+pub fn alias_set(name: &str, version: &str) -> Result<()> {
+    let version_normalized = normalize_version(version);
+    let mut dev_config = DevConfig::load()?;
+    dev_config
+        .version_aliases
+        .insert(name.to_string(), version_normalized.clone());
+    dev_config.save()?;
+
+    println!("{} {} -> {}", "✓".green(), name.cyan(), version_normalized);
+    if !config::version_dir(&version_normalized).exists() {
+        println!(
+            "{} {} is not installed yet; run `mpf-dev setup --version {}`",
+            "Note:".yellow(),
+            version_normalized,
+            name
+        );
+    }
+    Ok(())
+}
+
+/// Alias --list: show all version aliases
+pub fn alias_list() -> Result<()> {
+    let dev_config = DevConfig::load()?;
+    if dev_config.version_aliases.is_empty() {
+        println!("No version aliases defined.");
+        return Ok(());
+    }
+
+    let mut aliases: Vec<_> = dev_config.version_aliases.iter().collect();
+    aliases.sort();
+    println!("{}", "Version aliases:".bold());
+    for (name, version) in aliases {
+        let marker = if config::version_dir(version).exists() {
+            "".normal()
+        } else {
+            " (not installed)".dimmed()
+        };
+        println!("  {} -> {}{}", name.cyan(), version, marker);
+    }
+    Ok(())
+}
+
 /// Use command: switch SDK version
-pub fn use_version(version: &str) -> Result<()> {
-    let version_normalized = if version.starts_with('v') {
-        version.to_string()
+pub async fn use_version(version: &str, install_if_missing: bool) -> Result<()> {
+    let mut dev_config = DevConfig::load().unwrap_or_default();
+    let version = match dev_config.version_aliases.get(version) {
+        Some(target) => target.clone(),
+        None => version.to_string(),
+    };
+    let version = version.as_str();
+
+    let version_normalized = if config::is_version_constraint(version) {
+        let installed = config::installed_versions();
+        match config::resolve_version_constraint(version, &installed) {
+            Some(v) => v,
+            None => {
+                bail!(
+                    "No installed version matches '{}'. Installed: {}",
+                    version,
+                    if installed.is_empty() {
+                        "none".to_string()
+                    } else {
+                        installed.join(", ")
+                    }
+                );
+            }
+        }
     } else {
-        format!("v{}", version)
+        normalize_version(version)
     };
 
     let version_dir = config::version_dir(&version_normalized);
 
+    if !version_dir.exists() && install_if_missing {
+        // setup installs the version and makes it current
+        return setup(SetupOptions {
+            version: Some(version_normalized),
+            prerelease: false,
+            arch: None,
+            token: None,
+            asset: None,
+            list_assets: false,
+            retries: 2,
+        })
+        .await;
+    }
+
     if !version_dir.exists() {
         bail!(
-            "Version {} is not installed. Run `mpf-dev setup --version {}`",
+            "Version {} is not installed. Run `mpf-dev setup --version {}` or pass --install-if-missing",
             version_normalized,
             version
         );
@@ -219,7 +782,6 @@ pub fn use_version(version: &str) -> Result<()> {
     config::set_current_version(&version_normalized)?;
 
     // Update dev.json
-    let mut dev_config = DevConfig::load().unwrap_or_default();
     dev_config.sdk_version = Some(version_normalized.clone());
     dev_config.save()?;
 
@@ -231,3 +793,195 @@ pub fn use_version(version: &str) -> Result<()> {
 
     Ok(())
 }
+
+/// SDK uninstall command: remove a specific installed version
+pub fn sdk_uninstall(version: &str, force: bool) -> Result<()> {
+    let version_normalized = require_installed(version)?;
+    let is_current = config::current_version().as_deref() == Some(version_normalized.as_str());
+
+    if is_current && !force {
+        bail!(
+            "Version {} is the active SDK. Switch with `mpf-dev use <other>` first, or pass --force.",
+            version_normalized
+        );
+    }
+
+    let version_dir = config::version_dir(&version_normalized);
+    println!("{} Removing {}...", "->".cyan(), version_dir.display());
+    fs::remove_dir_all(&version_dir)
+        .with_context(|| format!("Failed to remove {}", version_dir.display()))?;
+
+    if is_current {
+        config::clear_current_version()?;
+
+        let mut dev_config = DevConfig::load().unwrap_or_default();
+        dev_config.sdk_version = None;
+        dev_config.save()?;
+    }
+
+    println!("{} SDK {} uninstalled", "✓".green(), version_normalized);
+
+    if is_current {
+        let remaining = config::installed_versions();
+        if remaining.is_empty() {
+            println!(
+                "  {} No SDK versions left. Run {} to install one.",
+                "Note:".yellow(),
+                "mpf-dev setup".cyan()
+            );
+        } else {
+            println!(
+                "  {} No active SDK. Run {} to select one of: {}",
+                "Note:".yellow(),
+                "mpf-dev use <version>".cyan(),
+                remaining.join(", ")
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// SDK verify command: check the directory layout of an installed version
+pub fn sdk_verify(version: &str) -> Result<()> {
+    let version_normalized = require_installed(version)?;
+    let version_dir = config::version_dir(&version_normalized);
+
+    println!("Verifying SDK {}...", version_normalized.green());
+    println!("  Location: {}", version_dir.display());
+
+    let mut missing = Vec::new();
+    for sub in ["bin", "lib", "qml", "include"] {
+        if version_dir.join(sub).is_dir() {
+            println!("  {} {}/", "✓".green(), sub);
+        } else {
+            println!("  {} {}/ {}", "✗".red(), sub, "(missing)".red());
+            missing.push(sub);
+        }
+    }
+
+    if !missing.is_empty() {
+        bail!(
+            "SDK {} is incomplete (missing: {}). Reinstall with `mpf-dev sdk uninstall {} && mpf-dev setup --version {}`",
+            version_normalized,
+            missing.join(", "),
+            version_normalized,
+            version_normalized
+        );
+    }
+
+    println!("{} SDK {} looks complete", "✓".green(), version_normalized);
+    Ok(())
+}
+
+/// SDK changelog command: print a release's notes (default: the active version)
+pub async fn show_changelog(version: Option<&str>, raw: bool) -> Result<()> {
+    let tag = match version {
+        Some(v) => normalize_version(v),
+        None => config::current_version()
+            .context("No SDK version is active. Pass a version, e.g. `mpf-dev sdk changelog v1.0.33`")?,
+    };
+    let token = github_token(&DevConfig::load().unwrap_or_default());
+    let release = fetch_release(Some(&tag), token.as_deref()).await?;
+
+    let body = release["body"].as_str().unwrap_or_default().replace('\r', "");
+    let text = if raw {
+        body
+    } else {
+        let title = release["name"]
+            .as_str()
+            .filter(|n| !n.is_empty())
+            .unwrap_or(&tag);
+        let notes = if body.trim().is_empty() {
+            "(no release notes)".dimmed().to_string()
+        } else {
+            render_markdown(&body)
+        };
+        format!("{}\n\n{}", title.bold().cyan(), notes)
+    };
+
+    if std::io::stdout().is_terminal() && page(&text) {
+        return Ok(());
+    }
+    println!("{}", text.trim_end());
+    Ok(())
+}
+
+/// Show text through `less`, falling back to `more`; false if neither runs
+fn page(text: &str) -> bool {
+    let pagers: [(&str, &[&str]); 2] = [("less", &["-R", "-F", "-X"]), ("more", &[])];
+    for (program, args) in pagers {
+        let child = std::process::Command::new(program)
+            .args(args)
+            .stdin(std::process::Stdio::piped())
+            .spawn();
+        let Ok(mut child) = child else {
+            continue;
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            // The user may quit the pager early; a broken pipe is fine
+            let _ = stdin.write_all(text.as_bytes());
+        }
+        let _ = child.wait();
+        return true;
+    }
+    false
+}
+
+/// Render release-note Markdown for the terminal: headings, bullets,
+/// fenced code blocks, `**bold**` and `code` spans
+fn render_markdown(markdown: &str) -> String {
+    let mut out = String::new();
+    let mut in_code = false;
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            out.push_str(&format!("    {}\n", line.dimmed()));
+            continue;
+        }
+        let trimmed = line.trim_start();
+        let indent = &line[..line.len() - trimmed.len()];
+        let rendered = if let Some(heading) = trimmed.strip_prefix('#') {
+            let heading = heading.trim_start_matches('#').trim();
+            render_inline(heading).bold().underline().to_string()
+        } else if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            format!("{}  • {}", indent, render_inline(item))
+        } else {
+            format!("{}{}", indent, render_inline(trimmed))
+        };
+        out.push_str(&rendered);
+        out.push('\n');
+    }
+    out
+}
+
+/// `**bold**` and `code` spans of one line
+fn render_inline(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("**") {
+            if let Some(end) = after.find("**") {
+                out.push_str(&after[..end].bold().to_string());
+                rest = &after[end + 2..];
+                continue;
+            }
+        } else if let Some(after) = rest.strip_prefix('`') {
+            if let Some(end) = after.find('`') {
+                out.push_str(&after[..end].cyan().to_string());
+                rest = &after[end + 1..];
+                continue;
+            }
+        }
+        let ch = rest.chars().next().unwrap_or_default();
+        out.push(ch);
+        rest = &rest[ch.len_utf8()..];
+    }
+    out
+}
@@ -1,9 +1,24 @@
 use anyhow::{bail, Context, Result};
 use colored::*;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::env;
 use std::fs;
-use std::path::PathBuf;
-use std::process::Command;
+use std::io::{IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use crate::config::{self, DevConfig};
+
+use super::{confirm, detect_qt_path, is_multi_config, library_path_vars, shell_command};
+use super::init::reinit_all;
+use super::setup::{asset_request, fetch_release, github_token};
+
+/// Release asset describing the component commits of an SDK release
+const MANIFEST_ASSET: &str = "workspace-manifest.json";
 
 const WORKSPACE_REPOS: &[(&str, &str)] = &[
     ("mpf-sdk", "https://github.com/QMPF/mpf-sdk.git"),
@@ -26,6 +41,93 @@ const WORKSPACE_REPOS: &[(&str, &str)] = &[
     ),
 ];
 
+/// dev.json component name, workspace repo directory and CMake target
+const WORKSPACE_TARGETS: &[(&str, &str, &str)] = &[
+    ("sdk", "mpf-sdk", "mpf-sdk"),
+    ("http-client", "mpf-http-client", "mpf-http-client"),
+    ("ui-components", "mpf-ui-components", "mpf-ui-components"),
+    ("host", "mpf-host", "mpf-host"),
+    ("plugin-orders", "mpf-plugin-orders", "orders-plugin"),
+    ("plugin-rules", "mpf-plugin-rules", "rules-plugin"),
+];
+
+/// A repository added with `workspace add`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct CustomRepo {
+    name: String,
+    url: String,
+}
+
+/// Contents of the .mpf-workspace marker file
+#[derive(Debug, Serialize, Deserialize)]
+struct WorkspaceMarker {
+    /// Clone URL scheme: "https" or "ssh"
+    scheme: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fork_org: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    custom_repos: Vec<CustomRepo>,
+    /// Built-in repos removed with `workspace remove`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    excluded: Vec<String>,
+    /// CMake generator chosen at `workspace init` (used by the presets and builds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    generator: Option<String>,
+}
+
+impl Default for WorkspaceMarker {
+    fn default() -> Self {
+        Self {
+            scheme: "https".to_string(),
+            fork_org: None,
+            custom_repos: Vec::new(),
+            excluded: Vec::new(),
+            generator: None,
+        }
+    }
+}
+
+impl WorkspaceMarker {
+    /// Read the marker, accepting both JSON and the older `key = value` format
+    fn load(workspace: &Path) -> Self {
+        let content = match fs::read_to_string(workspace.join(".mpf-workspace")) {
+            Ok(c) => c,
+            Err(_) => return Self::default(),
+        };
+        if let Ok(marker) = serde_json::from_str(&content) {
+            return marker;
+        }
+
+        let mut marker = Self::default();
+        for line in content.lines() {
+            if let Some((key, value)) = line.split_once('=') {
+                match key.trim() {
+                    "scheme" => marker.scheme = value.trim().to_string(),
+                    "fork_org" => marker.fork_org = Some(value.trim().to_string()),
+                    _ => {}
+                }
+            }
+        }
+        marker
+    }
+
+    fn save(&self, workspace: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)? + "\n";
+        fs::write(workspace.join(".mpf-workspace"), content)?;
+        Ok(())
+    }
+
+    /// Names of the repos in this workspace: built-ins not removed, then custom repos
+    fn repos(&self) -> Vec<&str> {
+        WORKSPACE_REPOS
+            .iter()
+            .map(|(name, _)| *name)
+            .filter(|name| !self.excluded.iter().any(|e| e == name))
+            .chain(self.custom_repos.iter().map(|r| r.name.as_str()))
+            .collect()
+    }
+}
+
 /// Find workspace root by looking for .mpf-workspace marker
 fn find_workspace_root() -> Option<PathBuf> {
     let mut current = env::current_dir().ok()?;
@@ -39,257 +141,1933 @@ fn find_workspace_root() -> Option<PathBuf> {
     }
 }
 
+/// Transform `https://github.com/ORG/REPO.git` into `git@github.com:ORG/REPO.git`
+fn to_ssh_url(https_url: &str) -> String {
+    match https_url.strip_prefix("https://") {
+        Some(rest) => match rest.split_once('/') {
+            Some((host, path)) => format!("git@{}:{}", host, path),
+            None => https_url.to_string(),
+        },
+        None => https_url.to_string(),
+    }
+}
+
+/// Replace the organization in `https://github.com/ORG/REPO.git`
+fn with_fork_org(https_url: &str, org: &str) -> String {
+    let repo = https_url.rsplit('/').next().unwrap_or(https_url);
+    match https_url.strip_suffix(repo) {
+        Some(base) => {
+            let base = base.trim_end_matches('/');
+            let host = base.rsplit_once('/').map(|(h, _)| h).unwrap_or(base);
+            format!("{}/{}/{}", host, org, repo)
+        }
+        None => https_url.to_string(),
+    }
+}
+
+/// Oldest git with the clone/fetch options the workspace commands rely on
+const MIN_GIT_VERSION: (u32, u32) = (2, 20);
+/// Oldest CMake that reads version 6 CMakePresets.json
+const MIN_CMAKE_VERSION: (u32, u32) = (3, 25);
+
+/// Run `<tool> --version` and return the version number it reports
+/// (`git version 2.39.2.windows.1` → `2.39.2.windows.1`)
+fn tool_version(tool: &str) -> Option<String> {
+    let output = Command::new(tool).arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let first = stdout.lines().next()?.trim().to_string();
+    let version = first
+        .split_whitespace()
+        .find(|w| w.starts_with(|c: char| c.is_ascii_digit()))
+        .unwrap_or(&first);
+    Some(version.to_string())
+}
+
+/// `major.minor` of a version string, ignoring anything after
+fn major_minor(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version.split('.').map(|p| p.parse::<u32>().ok());
+    Some((parts.next()??, parts.next()??))
+}
+
+/// Platform-specific hint for installing a missing tool
+fn install_hint(package: &str) -> String {
+    if cfg!(windows) {
+        format!("Install it with `winget install {}` or add it to PATH.", package)
+    } else if cfg!(target_os = "macos") {
+        format!("Install it with `brew install {}`.", package.to_lowercase())
+    } else {
+        format!(
+            "Install it with your package manager, e.g. `sudo apt install {}`.",
+            package.to_lowercase()
+        )
+    }
+}
+
+/// Make sure git is installed; warns when it is older than 2.20
+fn check_git_available() -> Result<String> {
+    let Some(version) = tool_version("git") else {
+        let package = if cfg!(windows) { "Git.Git" } else { "git" };
+        bail!("git not found. {}", install_hint(package));
+    };
+    if major_minor(&version).is_some_and(|v| v < MIN_GIT_VERSION) {
+        println!(
+            "{} git {} is older than {}.{}; clones and updates may fail",
+            "Warning:".yellow(),
+            version,
+            MIN_GIT_VERSION.0,
+            MIN_GIT_VERSION.1
+        );
+    }
+    Ok(version)
+}
+
+/// Make sure cmake is installed; warns when it can't read the workspace presets
+fn check_cmake_available() -> Result<String> {
+    let Some(version) = tool_version("cmake") else {
+        let package = if cfg!(windows) { "Kitware.CMake" } else { "cmake" };
+        bail!("cmake not found. {}", install_hint(package));
+    };
+    if major_minor(&version).is_some_and(|v| v < MIN_CMAKE_VERSION) {
+        println!(
+            "{} cmake {} is older than {}.{}; CMakePresets.json will not be read",
+            "Warning:".yellow(),
+            version,
+            MIN_CMAKE_VERSION.0,
+            MIN_CMAKE_VERSION.1
+        );
+    }
+    Ok(version)
+}
+
+/// Generator used when `workspace init` is not given one
+#[cfg(target_os = "macos")]
+const DEFAULT_WORKSPACE_GENERATOR: &str = "Xcode";
+#[cfg(not(target_os = "macos"))]
+const DEFAULT_WORKSPACE_GENERATOR: &str = "Ninja";
+
+/// Make sure ninja is installed (the default workspace generator)
+fn check_ninja_available() -> Result<()> {
+    if tool_version("ninja").is_none() {
+        bail!(
+            "ninja not found. {} Or pick another generator with -G.",
+            install_hint(if cfg!(windows) { "Ninja-build.Ninja" } else { "ninja-build" })
+        );
+    }
+    Ok(())
+}
+
+/// Phase and percentage of a git progress line
+/// (`Receiving objects:  45% (123/456)` → `("Receiving objects", 45)`)
+fn parse_git_progress(line: &str) -> Option<(String, u64)> {
+    let line = line.trim().trim_start_matches("remote:").trim();
+    let (phase, rest) = line.split_once(':')?;
+    let percent = rest.trim().split('%').next()?.trim().parse().ok()?;
+    Some((phase.to_string(), percent))
+}
+
+/// Environment variable carrying the GitHub token to the credential helper
+const GIT_TOKEN_ENV: &str = "MPF_GIT_TOKEN";
+
+/// `git` command that authenticates HTTPS GitHub clones with `token`.
+///
+/// The token is handed to an inline credential helper through the
+/// environment, so it never shows up in the process arguments, the clone
+/// URL or the cloned repo's `.git/config`.
+fn git_command(url: &str, token: Option<&str>) -> Command {
+    let mut git = Command::new("git");
+    if let Some(token) = token.filter(|_| url.starts_with("https://github.com/")) {
+        git.args(["-c", "credential.helper="])
+            .arg("-c")
+            .arg(format!(
+                "credential.helper=!f() {{ test \"$1\" = get && echo username=x-access-token && echo \"password=${}\"; }}; f",
+                GIT_TOKEN_ENV
+            ))
+            .env(GIT_TOKEN_ENV, token);
+    }
+    git
+}
+
+/// `git clone --progress`, driving a progress bar from git's progress output
+fn clone_with_progress(
+    progress: &MultiProgress,
+    name: &str,
+    url: &str,
+    dest: &Path,
+    token: Option<&str>,
+) -> Result<()> {
+    let pb = progress.add(ProgressBar::new(100));
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{prefix:>20} [{bar:30.cyan/blue}] {pos:>3}% {msg}")?
+            .progress_chars("#>-"),
+    );
+    pb.set_prefix(name.to_string());
+
+    let mut child = git_command(url, token)
+        .args(["clone", "--progress", url])
+        .arg(dest)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run git clone")?;
+
+    // git separates progress updates with \r and messages with \n
+    let mut stderr = child.stderr.take().context("Failed to read git output")?;
+    let mut messages = Vec::new();
+    let mut line = Vec::new();
+    let mut buf = [0u8; 4096];
+    loop {
+        let n = stderr.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        for &byte in &buf[..n] {
+            if byte != b'\r' && byte != b'\n' {
+                line.push(byte);
+                continue;
+            }
+            let text = String::from_utf8_lossy(&line).to_string();
+            line.clear();
+            match parse_git_progress(&text) {
+                Some((phase, percent)) => {
+                    pb.set_message(phase);
+                    pb.set_position(percent);
+                }
+                None if !text.trim().is_empty() => messages.push(text),
+                None => {}
+            }
+        }
+    }
+
+    let status = child.wait().context("Failed to run git clone")?;
+    if !status.success() {
+        pb.abandon_with_message("failed".red().to_string());
+        for message in &messages {
+            progress.suspend(|| eprintln!("  {}", message));
+        }
+        bail!("Failed to clone {}", name);
+    }
+    pb.set_position(100);
+    pb.finish_with_message("done".green().to_string());
+    Ok(())
+}
+
 /// Workspace init: create workspace and clone all components
-pub fn workspace_init(path: Option<String>) -> Result<()> {
+pub fn workspace_init(
+    path: Option<String>,
+    no_vscode: bool,
+    ssh: bool,
+    fork_org: Option<&str>,
+    no_clone: bool,
+    generator: Option<String>,
+) -> Result<()> {
     let workspace_dir = path
         .map(PathBuf::from)
         .unwrap_or_else(|| env::current_dir().unwrap());
 
     println!("{}", "MPF Workspace Initialization".bold().cyan());
     println!("Directory: {}", workspace_dir.display());
+    let token = github_token(&DevConfig::load()?);
+    if !no_clone {
+        let git_version = check_git_available()?;
+        println!("git: {}", git_version);
+        if token.is_some() && !ssh {
+            println!("Authentication: GitHub token");
+        }
+    }
     println!();
 
     fs::create_dir_all(&workspace_dir)?;
 
-    // Create workspace marker
-    let marker_path = workspace_dir.join(".mpf-workspace");
-    fs::write(&marker_path, "# MPF Workspace\n")?;
+    // Create workspace marker (records the clone URL scheme for later updates);
+    // repos added earlier with `workspace add` are kept
+    let previous = WorkspaceMarker::load(&workspace_dir);
+    let marker = WorkspaceMarker {
+        scheme: if ssh { "ssh" } else { "https" }.to_string(),
+        fork_org: fork_org.map(str::to_string),
+        custom_repos: previous.custom_repos,
+        excluded: previous.excluded,
+        generator: generator.or(previous.generator),
+    };
+    marker.save(&workspace_dir)?;
 
-    // Clone all repos
+    // Clone all repos, one progress bar per repo
+    let progress = MultiProgress::new();
+    let mut not_cloned = Vec::new();
     for (name, url) in WORKSPACE_REPOS {
         let repo_dir = workspace_dir.join(name);
 
-        if repo_dir.exists() {
-            println!("{} {} (already exists)", "->".yellow(), name);
+        if marker.excluded.iter().any(|e| e == name) {
+            progress.suspend(|| println!("{} {} (removed from workspace)", "->".dimmed(), name));
+            continue;
+        }
+        if repo_dir.exists() {
+            progress.suspend(|| println!("{} {} (already exists)", "->".yellow(), name));
+            continue;
+        }
+
+        let url = match fork_org {
+            Some(org) => with_fork_org(url, org),
+            None => url.to_string(),
+        };
+        let url = if ssh { to_ssh_url(&url) } else { url };
+
+        if no_clone {
+            not_cloned.push((name, url));
+            continue;
+        }
+
+        progress.suspend(|| println!("{} Cloning {} ({})...", "->".cyan(), name, url.dimmed()));
+        clone_with_progress(&progress, name, &url, &repo_dir, token.as_deref())?;
+    }
+
+    if !not_cloned.is_empty() {
+        println!(
+            "{} {} repo(s) missing (--no-clone). Clone them manually:",
+            "Warning:".yellow(),
+            not_cloned.len()
+        );
+        for (name, url) in &not_cloned {
+            println!("  git clone {} {}", url, name);
+        }
+        println!();
+    }
+
+    // Create top-level CMakeLists.txt
+    let cmake_content =
+        generate_workspace_cmake(&workspace_dir, &marker.excluded, &marker.custom_repos);
+    fs::write(workspace_dir.join("CMakeLists.txt"), cmake_content)?;
+
+    // Create CMakePresets.json for easy Qt Creator integration
+    let presets_content = generate_cmake_presets(
+        marker.generator.as_deref().unwrap_or(DEFAULT_WORKSPACE_GENERATOR),
+    );
+    fs::write(workspace_dir.join("CMakePresets.json"), presets_content)?;
+
+    // Create .gitignore (kept if the user already has one)
+    let gitignore_path = workspace_dir.join(".gitignore");
+    if !gitignore_path.exists() {
+        fs::write(&gitignore_path, generate_workspace_gitignore())?;
+        println!("{} Generated {}", "->".cyan(), gitignore_path.display());
+    }
+    check_component_gitignores(&workspace_dir, &marker);
+
+    // Create VS Code multi-root workspace
+    if !no_vscode {
+        let vscode_path = workspace_dir.join(".mpf-workspace.code-workspace");
+        fs::write(
+            &vscode_path,
+            generate_vscode_workspace(&workspace_dir, &marker)?,
+        )?;
+        println!("{} Generated {}", "->".cyan(), vscode_path.display());
+    }
+
+    println!();
+    println!("{} Workspace initialized!", "[OK]".green());
+    println!();
+    println!("Next steps:");
+    println!(
+        "  1. Open {} in Qt Creator",
+        workspace_dir.join("CMakeLists.txt").display()
+    );
+    println!("  2. Configure with MinGW kit");
+    println!("  3. Build and run");
+    println!();
+    println!("Or use CLI:");
+    println!("  cd {}", workspace_dir.display());
+    println!("  mpf-dev workspace build");
+    println!("  mpf-dev workspace run");
+
+    Ok(())
+}
+
+/// Repo directory name from a clone URL (`.../mpf-plugin-foo.git` → `mpf-plugin-foo`)
+fn repo_name_from_url(url: &str) -> String {
+    let last = url
+        .trim_end_matches('/')
+        .rsplit(['/', ':'])
+        .next()
+        .unwrap_or(url);
+    last.strip_suffix(".git").unwrap_or(last).to_string()
+}
+
+/// Rewrite CMakeLists.txt (and the VS Code workspace, if present) after the
+/// set of repos changed
+fn regenerate_workspace_files(workspace: &Path, marker: &WorkspaceMarker) -> Result<()> {
+    fs::write(
+        workspace.join("CMakeLists.txt"),
+        generate_workspace_cmake(workspace, &marker.excluded, &marker.custom_repos),
+    )?;
+    let vscode_path = workspace.join(".mpf-workspace.code-workspace");
+    if vscode_path.exists() {
+        fs::write(&vscode_path, generate_vscode_workspace(workspace, marker)?)?;
+    }
+    Ok(())
+}
+
+/// Workspace add: clone an extra repo and include it in the workspace build
+pub fn workspace_add(repo_url: &str, name: Option<&str>) -> Result<()> {
+    let workspace = find_workspace_root()
+        .context("Not in an MPF workspace. Run 'mpf-dev workspace init' first.")?;
+    let name = name
+        .map(str::to_string)
+        .unwrap_or_else(|| repo_name_from_url(repo_url));
+
+    let mut marker = WorkspaceMarker::load(&workspace);
+    if WORKSPACE_REPOS.iter().any(|(n, _)| *n == name)
+        || marker.custom_repos.iter().any(|r| r.name == name)
+    {
+        bail!("Repository '{}' is already part of the workspace", name);
+    }
+
+    let repo_dir = workspace.join(&name);
+    if repo_dir.exists() {
+        println!("{} {} (already exists)", "->".yellow(), name);
+    } else {
+        println!("{} Cloning {} ({})...", "->".cyan(), name, repo_url.dimmed());
+        let token = github_token(&DevConfig::load()?);
+        let status = git_command(repo_url, token.as_deref())
+            .args(["clone", repo_url, &repo_dir.to_string_lossy()])
+            .status()
+            .context("Failed to run git clone")?;
+        if !status.success() {
+            bail!("Failed to clone {}", name);
+        }
+    }
+
+    marker.custom_repos.push(CustomRepo {
+        name: name.clone(),
+        url: repo_url.to_string(),
+    });
+    marker.save(&workspace)?;
+    regenerate_workspace_files(&workspace, &marker)?;
+
+    println!("{} Added {} to the workspace", "[OK]".green(), name);
+    if !repo_dir.join("CMakeLists.txt").exists() {
+        println!(
+            "{} {} has no CMakeLists.txt; add_subdirectory will fail until it does",
+            "Warning:".yellow(),
+            name
+        );
+    }
+    Ok(())
+}
+
+/// Workspace remove: drop a repo from the workspace build
+pub fn workspace_remove(name: &str, keep_source: bool) -> Result<()> {
+    let workspace = find_workspace_root()
+        .context("Not in an MPF workspace. Run 'mpf-dev workspace init' first.")?;
+    let mut marker = WorkspaceMarker::load(&workspace);
+
+    let built_in = WORKSPACE_REPOS.iter().any(|(n, _)| *n == name);
+    if built_in {
+        if marker.excluded.iter().any(|e| e == name) {
+            bail!("Repository '{}' was already removed from the workspace", name);
+        }
+        // Remembered so a later `workspace init` does not clone it again
+        marker.excluded.push(name.to_string());
+    } else {
+        let before = marker.custom_repos.len();
+        marker.custom_repos.retain(|r| r.name != name);
+        if marker.custom_repos.len() == before {
+            bail!("Repository '{}' is not part of the workspace", name);
+        }
+    }
+    marker.save(&workspace)?;
+    regenerate_workspace_files(&workspace, &marker)?;
+
+    let repo_dir = workspace.join(name);
+    if !keep_source && repo_dir.exists() {
+        fs::remove_dir_all(&repo_dir)
+            .with_context(|| format!("Failed to remove {}", repo_dir.display()))?;
+        println!("{} Deleted {}", "->".cyan(), repo_dir.display());
+    }
+
+    println!("{} Removed {} from the workspace", "[OK]".green(), name);
+    if matches!(name, "mpf-sdk" | "mpf-http-client" | "mpf-ui-components") {
+        println!(
+            "{} Other components link against {}; the workspace build may fail without it",
+            "Warning:".yellow(),
+            name
+        );
+    }
+    Ok(())
+}
+
+/// Workspace repos list: built-in and custom repositories
+pub fn workspace_repos_list() -> Result<()> {
+    let workspace = find_workspace_root()
+        .context("Not in an MPF workspace. Run 'mpf-dev workspace init' first.")?;
+    let marker = WorkspaceMarker::load(&workspace);
+
+    let mark = |name: &str| {
+        if workspace.join(name).exists() {
+            "✓".green()
+        } else {
+            "✗".red()
+        }
+    };
+
+    println!("{}", "Built-in repositories:".bold());
+    for (name, url) in WORKSPACE_REPOS {
+        if marker.excluded.iter().any(|e| e == name) {
+            println!("  {} {} {}", "○".dimmed(), name.dimmed(), "(removed)".dimmed());
+        } else {
+            println!("  {} {} {}", mark(name), name, url.dimmed());
+        }
+    }
+    println!();
+    println!("{}", "Custom repositories:".bold());
+    if marker.custom_repos.is_empty() {
+        println!("  {} None (add with `mpf-dev workspace add <url>`)", "○".dimmed());
+    }
+    for repo in &marker.custom_repos {
+        println!("  {} {} {}", mark(&repo.name), repo.name, repo.url.dimmed());
+    }
+    Ok(())
+}
+
+/// Options for `workspace build`
+pub struct BuildOptions {
+    /// CMAKE_BUILD_TYPE used at configure time
+    pub config: String,
+    /// Build only this CMake target
+    pub target: Option<String>,
+    /// Parallel job count (default: let the generator decide)
+    pub jobs: Option<usize>,
+    /// Pass --verbose to cmake --build
+    pub verbose: bool,
+    /// CMake preset for configure and build
+    pub preset: Option<String>,
+    /// CMake generator (default: the one chosen at `workspace init`)
+    pub generator: Option<String>,
+    /// Install the build into the active SDK directory afterwards
+    pub install: bool,
+    /// Build components one at a time and print per-component timings
+    pub summary: bool,
+    /// Compile and link with --coverage for `workspace coverage`
+    pub coverage: bool,
+}
+
+/// Compiler/linker flag variables set for coverage builds
+const COVERAGE_FLAG_VARS: &[&str] = &[
+    "CMAKE_CXX_FLAGS",
+    "CMAKE_EXE_LINKER_FLAGS",
+    "CMAKE_SHARED_LINKER_FLAGS",
+];
+
+/// Value of a `NAME:TYPE=value` entry in build/CMakeCache.txt
+fn cmake_cache_value(build_dir: &Path, name: &str) -> Option<String> {
+    let content = fs::read_to_string(build_dir.join("CMakeCache.txt")).ok()?;
+    content.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        (key.split(':').next()? == name).then(|| value.to_string())
+    })
+}
+
+/// Whether the configured build has coverage instrumentation (None if unconfigured)
+fn cache_has_coverage(build_dir: &Path) -> Option<bool> {
+    build_dir.join("CMakeCache.txt").exists().then(|| {
+        cmake_cache_value(build_dir, "CMAKE_CXX_FLAGS").is_some_and(|f| f.contains("--coverage"))
+    })
+}
+
+/// Build time of one component, for `workspace build --summary`
+struct ComponentTiming {
+    name: String,
+    elapsed: Duration,
+    ok: bool,
+}
+
+/// Run one build step and record how long it took
+fn timed(
+    timings: &mut Vec<ComponentTiming>,
+    name: &str,
+    step: impl FnOnce() -> Result<()>,
+) -> Result<()> {
+    let started = Instant::now();
+    let result = step();
+    timings.push(ComponentTiming {
+        name: name.to_string(),
+        elapsed: started.elapsed(),
+        ok: result.is_ok(),
+    });
+    result
+}
+
+/// Print the per-component timing table, slowest first
+fn print_build_summary(timings: &mut [ComponentTiming], total: Duration) {
+    timings.sort_by_key(|t| std::cmp::Reverse(t.elapsed));
+
+    println!();
+    println!("{}", "Build summary:".bold());
+    println!("  {:<24} {:>10}  RESULT", "COMPONENT", "TIME");
+    for t in timings.iter() {
+        let result = if t.ok { "ok".green() } else { "FAILED".red() };
+        println!(
+            "  {:<24} {:>10}  {}",
+            t.name,
+            format!("{:.1}s", t.elapsed.as_secs_f64()),
+            result
+        );
+    }
+    println!(
+        "  {:<24} {:>10}",
+        "Total".bold(),
+        format!("{:.1}s", total.as_secs_f64())
+    );
+}
+
+/// `cmake --build` for the workspace with the common options applied
+fn cmake_build(opts: &BuildOptions, workspace: &Path, multi_config: bool) -> Command {
+    let mut build = Command::new("cmake");
+    build.current_dir(workspace);
+    match &opts.preset {
+        Some(preset) => build.args(["--build", "--preset", preset]),
+        None => build.args(["--build", "build"]),
+    };
+    if multi_config && opts.preset.is_none() {
+        build.args(["--config", &opts.config]);
+    }
+    match opts.jobs {
+        Some(jobs) => build.args(["-j", &jobs.to_string()]),
+        None => build.arg("-j"),
+    };
+    if opts.verbose {
+        build.arg("--verbose");
+    }
+    build
+}
+
+/// Build one CMake target (or everything when `target` is None)
+fn run_cmake_build(
+    opts: &BuildOptions,
+    workspace: &Path,
+    multi_config: bool,
+    target: Option<&str>,
+) -> Result<()> {
+    let mut build = cmake_build(opts, workspace, multi_config);
+    if let Some(target) = target {
+        build.args(["--target", target]);
+    }
+    let status = build.status().context("Failed to run cmake build")?;
+    if !status.success() {
+        match target {
+            Some(target) => bail!("Build of {} failed", target),
+            None => bail!("Build failed"),
+        }
+    }
+    Ok(())
+}
+
+/// Run a component's custom build command from its repo directory
+fn run_build_command(workspace: &Path, name: &str, repo: &str, cmd: &str, config: &str) -> Result<()> {
+    println!("{} Building {}: {}", "->".cyan(), name, cmd.dimmed());
+    let status = shell_command(cmd)
+        .current_dir(workspace.join(repo))
+        .env("MPF_BUILD_COMPONENT", name)
+        .env("MPF_BUILD_CONFIG", config)
+        .status()
+        .with_context(|| format!("Failed to run build command for {}", name))?;
+    if !status.success() {
+        bail!("Build command for {} failed ({})", name, status);
+    }
+    Ok(())
+}
+
+/// CMake file API query for the list of files the build system depends on
+const CMAKE_FILES_QUERY: &str = ".cmake/api/v1/query/cmakeFiles-v1";
+
+/// Inputs recorded in the CMake file API `cmakeFiles` reply, if present
+fn file_api_inputs(build_dir: &Path) -> Option<Vec<PathBuf>> {
+    let reply_dir = build_dir.join(".cmake/api/v1/reply");
+    let reply = fs::read_dir(&reply_dir)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .find(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("cmakeFiles-v1-") && n.ends_with(".json"))
+        })?;
+    let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(reply).ok()?).ok()?;
+    let source = PathBuf::from(json["paths"]["source"].as_str()?);
+    let inputs = json["inputs"]
+        .as_array()?
+        .iter()
+        // Files shipped with CMake never change, and generated files are
+        // rewritten by every configure
+        .filter(|input| {
+            !input["isCMake"].as_bool().unwrap_or(false)
+                && !input["isGenerated"].as_bool().unwrap_or(false)
+        })
+        .filter_map(|input| input["path"].as_str())
+        .map(|path| source.join(path))
+        .collect();
+    Some(inputs)
+}
+
+/// Reason the workspace needs a fresh configure, or None for an incremental build
+fn reconfigure_reason(workspace: &Path, build_dir: &Path) -> Option<String> {
+    let cache = build_dir.join("CMakeCache.txt");
+    let cache_time = match fs::metadata(&cache).and_then(|m| m.modified()) {
+        Ok(t) => t,
+        Err(_) => return Some("no CMakeCache.txt".to_string()),
+    };
+    let newer = |path: &Path| {
+        fs::metadata(path)
+            .and_then(|m| m.modified())
+            .is_ok_and(|t| t > cache_time)
+    };
+
+    let mut files = Vec::new();
+    if collect_files(workspace, workspace, false, &mut files).is_ok() {
+        let changed = files.iter().find(|f| {
+            matches!(
+                f.file_name().and_then(|n| n.to_str()),
+                Some("CMakeLists.txt") | Some("CMakePresets.json")
+            ) && newer(&workspace.join(f))
+        });
+        if let Some(file) = changed {
+            return Some(format!("{} changed", file.display()));
+        }
+    }
+
+    // A query without a reply means the last configure never finished
+    let query = build_dir.join(CMAKE_FILES_QUERY);
+    match file_api_inputs(build_dir) {
+        Some(inputs) => {
+            if let Some(input) = inputs.iter().find(|p| newer(p)) {
+                let shown = input.strip_prefix(workspace).unwrap_or(input);
+                return Some(format!("{} changed", shown.display()));
+            }
+        }
+        None if query.exists() => {
+            return Some("previous configure did not complete".to_string());
+        }
+        None => {}
+    }
+    None
+}
+
+/// Workspace build: build all components
+pub fn workspace_build(opts: BuildOptions) -> Result<()> {
+    let workspace = find_workspace_root()
+        .context("Not in an MPF workspace. Run 'mpf-dev workspace init' first.")?;
+
+    let cmake_version = check_cmake_available()?;
+
+    println!("{}", "Building MPF Workspace".bold().cyan());
+    println!("Directory: {}", workspace.display());
+    println!("CMake: {}", cmake_version);
+    match &opts.preset {
+        Some(preset) => println!("Preset: {}", preset),
+        None => println!("Configuration: {}", opts.config),
+    }
+    if let Some(target) = &opts.target {
+        println!("Target: {}", target);
+    }
+    println!();
+
+    // Components with a build_command in dev.json are built by that command
+    let dev_config = DevConfig::load()?;
+    let custom: Vec<(&str, &str, &str, &str)> = WORKSPACE_TARGETS
+        .iter()
+        .filter_map(|&(name, repo, target)| {
+            dev_config
+                .components
+                .get(name)
+                .and_then(|c| c.build_command.as_deref())
+                .map(|cmd| (name, repo, target, cmd))
+        })
+        .collect();
+
+    if let Some(wanted) = &opts.target {
+        if let Some(&(name, repo, _, cmd)) = custom
+            .iter()
+            .find(|(name, _, target, _)| name == wanted || target == wanted)
+        {
+            run_build_command(&workspace, name, repo, cmd, &opts.config)?;
+            println!();
+            println!("{} Build complete!", "[OK]".green());
+            return Ok(());
+        }
+    }
+
+    let build_dir = workspace.join("build");
+    let marker = WorkspaceMarker::load(&workspace);
+    let generator = opts
+        .generator
+        .as_deref()
+        .or(marker.generator.as_deref())
+        .unwrap_or(DEFAULT_WORKSPACE_GENERATOR);
+    let multi_config = is_multi_config(generator);
+
+    // Ask before building so the install doesn't wait on a prompt
+    let sdk_current = config::current_link();
+    if opts.install {
+        println!(
+            "{} --install overwrites files in the active SDK ({})",
+            "Warning:".yellow(),
+            sdk_current.display()
+        );
+        if !confirm("Install the workspace build into the SDK?")? {
+            bail!("Aborted");
+        }
+    }
+
+    let started = Instant::now();
+
+    // Configure if needed
+    let reason = reconfigure_reason(&workspace, &build_dir).or_else(|| {
+        cache_has_coverage(&build_dir)
+            .filter(|&has| has != opts.coverage)
+            .map(|_| "coverage flags changed".to_string())
+    });
+    if let Some(reason) = reason {
+        if generator.starts_with("Ninja") {
+            check_ninja_available()?;
+        }
+        let cache = build_dir.join("CMakeCache.txt");
+        if cache.exists() {
+            println!("{} Reconfiguring ({})", "->".cyan(), reason);
+            fs::remove_file(&cache)
+                .with_context(|| format!("Failed to remove {}", cache.display()))?;
+        } else {
+            println!("{} Configuring CMake...", "->".cyan());
+        }
+
+        // Ask CMake to record its input files for the next staleness check
+        let query = build_dir.join(CMAKE_FILES_QUERY);
+        if let Some(parent) = query.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&query, "")?;
+
+        let mut configure = Command::new("cmake");
+        configure.current_dir(&workspace);
+        match &opts.preset {
+            Some(preset) => {
+                configure.args(["--preset", preset]);
+            }
+            None => {
+                configure.args(["-B", "build", "-G", generator]);
+                if !multi_config {
+                    configure.arg(format!("-DCMAKE_BUILD_TYPE={}", opts.config));
+                }
+            }
+        }
+        if opts.coverage {
+            for var in COVERAGE_FLAG_VARS {
+                configure.arg(format!("-D{}=--coverage", var));
+            }
+        }
+        let status = configure
+            .status()
+            .context("Failed to run cmake configure")?;
+
+        if !status.success() {
+            bail!("CMake configuration failed");
+        }
+    }
+
+    // Components cmake still builds: the SDK is an INTERFACE library with
+    // no build target of its own
+    let marker = WorkspaceMarker::load(&workspace);
+    let cmake_targets: Vec<(&str, &str)> = WORKSPACE_TARGETS
+        .iter()
+        .filter(|(name, repo, target)| {
+            *name != "sdk"
+                && !marker.excluded.iter().any(|e| e == repo)
+                && !custom.iter().any(|(_, _, t, _)| t == target)
+        })
+        .map(|&(name, _, target)| (name, target))
+        .collect();
+
+    // Build
+    println!("{} Building...", "->".cyan());
+
+    if opts.summary {
+        let mut timings = Vec::new();
+        let result = (|| -> Result<()> {
+            if let Some(target) = &opts.target {
+                return timed(&mut timings, target, || {
+                    run_cmake_build(&opts, &workspace, multi_config, Some(target))
+                });
+            }
+            for &(name, target) in &cmake_targets {
+                println!("{} Building {}...", "->".cyan(), name);
+                timed(&mut timings, name, || {
+                    run_cmake_build(&opts, &workspace, multi_config, Some(target))
+                })?;
+            }
+            if !marker.custom_repos.is_empty() {
+                println!("{} Building custom repositories...", "->".cyan());
+                timed(&mut timings, "(custom repos)", || {
+                    run_cmake_build(&opts, &workspace, multi_config, None)
+                })?;
+            }
+            for &(name, repo, _, cmd) in &custom {
+                timed(&mut timings, name, || {
+                    run_build_command(&workspace, name, repo, cmd, &opts.config)
+                })?;
+            }
+            Ok(())
+        })();
+        print_build_summary(&mut timings, started.elapsed());
+        result?;
+    } else {
+        let mut build = cmake_build(&opts, &workspace, multi_config);
+        if let Some(target) = &opts.target {
+            build.args(["--target", target]);
+        } else if !custom.is_empty() {
+            // Build everything cmake still owns, then the custom commands
+            build.arg("--target");
+            for (_, target) in &cmake_targets {
+                build.arg(target);
+            }
+        }
+
+        let status = build.status().context("Failed to run cmake build")?;
+
+        if !status.success() {
+            bail!("Build failed");
+        }
+
+        if opts.target.is_none() {
+            for &(name, repo, _, cmd) in &custom {
+                run_build_command(&workspace, name, repo, cmd, &opts.config)?;
+            }
+        }
+    }
+
+    println!();
+    println!("{} Build complete!", "[OK]".green());
+    println!();
+    println!("Output:");
+    let host_name = if cfg!(windows) {
+        "mpf-host.exe"
+    } else {
+        "mpf-host"
+    };
+    println!(
+        "  Host: {}",
+        build_dir.join("bin").join(host_name).display()
+    );
+    println!("  Plugins: {}", build_dir.join("plugins").display());
+    println!("  QML: {}", build_dir.join("qml").display());
+
+    if opts.install {
+        println!();
+        println!("{} Installing into {}...", "->".cyan(), sdk_current.display());
+        let mut install = Command::new("cmake");
+        install
+            .current_dir(&workspace)
+            .args(["--install", "build", "--prefix"])
+            .arg(&sdk_current);
+        if multi_config && opts.preset.is_none() {
+            install.args(["--config", &opts.config]);
+        }
+        let status = install.status().context("Failed to run cmake install")?;
+        if !status.success() {
+            bail!("Install failed");
+        }
+        println!("{} Installed into the active SDK", "[OK]".green());
+
+        if confirm("Regenerate CMakeUserPresets.json for all registered projects?")? {
+            reinit_all(&mut DevConfig::load()?)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Workspace run: run mpf-host from workspace
+pub fn workspace_run(args: Vec<String>) -> Result<()> {
+    let workspace = find_workspace_root()
+        .context("Not in an MPF workspace. Run 'mpf-dev workspace init' first.")?;
+
+    let build_dir = workspace.join("build");
+    let host_exe = if cfg!(windows) {
+        build_dir.join("bin").join("mpf-host.exe")
+    } else {
+        build_dir.join("bin").join("mpf-host")
+    };
+
+    if !host_exe.exists() {
+        bail!("mpf-host not found. Run 'mpf-dev workspace build' first.");
+    }
+
+    println!("{} Running mpf-host from workspace...", "->".cyan());
+
+    let mut cmd = Command::new(&host_exe);
+    cmd.current_dir(&workspace);
+    cmd.args(&args);
+
+    // Set library paths
+    #[cfg(windows)]
+    {
+        let current_path = env::var("PATH").unwrap_or_default();
+        let lib_path = format!(
+            "{};{};{}",
+            build_dir.join("bin").display(),
+            build_dir.join("plugins").display(),
+            current_path
+        );
+        cmd.env("PATH", lib_path);
+    }
+
+    #[cfg(unix)]
+    {
+        let lib_path = format!(
+            "{}:{}",
+            build_dir.join("bin").display(),
+            build_dir.join("plugins").display()
+        );
+        for var in library_path_vars() {
+            cmd.env(var, &lib_path);
+        }
+    }
+
+    cmd.env(
+        "QML_IMPORT_PATH",
+        build_dir.join("qml").to_string_lossy().to_string(),
+    );
+
+    let status = cmd.status()?;
+    std::process::exit(status.code().unwrap_or(1));
+}
+
+/// Run a git command in a repo and return trimmed stdout on success
+fn git_output(repo_dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .current_dir(repo_dir)
+        .args(args)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout)
+        .ok()
+        .map(|s| s.trim().to_string())
+}
+
+/// Per-repo state reported by `workspace status`
+#[derive(Serialize)]
+struct RepoStatus {
+    name: String,
+    exists: bool,
+    branch: Option<String>,
+    last_commit: Option<String>,
+    dirty: bool,
+    /// Commits ahead of the upstream branch (None if no upstream)
+    ahead_count: Option<u32>,
+}
+
+/// Machine-readable `workspace status --json` output
+#[derive(Serialize)]
+struct WorkspaceStatusOutput {
+    root: Option<String>,
+    repos: Vec<RepoStatus>,
+    built: bool,
+    host_path: Option<String>,
+}
+
+fn repo_status(ws: &Path, name: &str) -> RepoStatus {
+    let repo_dir = ws.join(name);
+    if !repo_dir.exists() {
+        return RepoStatus {
+            name: name.to_string(),
+            exists: false,
+            branch: None,
+            last_commit: None,
+            dirty: false,
+            ahead_count: None,
+        };
+    }
+
+    RepoStatus {
+        name: name.to_string(),
+        exists: true,
+        branch: git_output(&repo_dir, &["rev-parse", "--abbrev-ref", "HEAD"]),
+        last_commit: git_output(&repo_dir, &["log", "-1", "--oneline"]),
+        dirty: git_output(&repo_dir, &["status", "--porcelain"])
+            .map(|s| !s.is_empty())
+            .unwrap_or(false),
+        ahead_count: git_output(&repo_dir, &["rev-list", "--count", "@{u}..HEAD"])
+            .and_then(|s| s.parse().ok()),
+    }
+}
+
+fn workspace_host_exe(ws: &Path) -> PathBuf {
+    let bin = ws.join("build").join("bin");
+    if cfg!(windows) {
+        bin.join("mpf-host.exe")
+    } else {
+        bin.join("mpf-host")
+    }
+}
+
+/// Workspace status: show workspace info
+pub fn workspace_status(json: bool) -> Result<()> {
+    let workspace = find_workspace_root();
+
+    if json {
+        let output = match &workspace {
+            Some(ws) => {
+                let host_exe = workspace_host_exe(ws);
+                WorkspaceStatusOutput {
+                    root: Some(ws.to_string_lossy().to_string()),
+                    repos: WorkspaceMarker::load(ws)
+                        .repos()
+                        .into_iter()
+                        .map(|name| repo_status(ws, name))
+                        .collect(),
+                    built: host_exe.exists(),
+                    host_path: Some(host_exe.to_string_lossy().to_string()),
+                }
+            }
+            None => WorkspaceStatusOutput {
+                root: None,
+                repos: Vec::new(),
+                built: false,
+                host_path: None,
+            },
+        };
+        serde_json::to_writer_pretty(std::io::stdout(), &output)?;
+        println!();
+        return Ok(());
+    }
+
+    println!("{}", "MPF Workspace Status".bold().cyan());
+    println!();
+
+    if let Some(ws) = workspace {
+        println!("{} Workspace: {}", "[OK]".green(), ws.display());
+
+        // Check each component
+        for name in WorkspaceMarker::load(&ws).repos() {
+            let repo = repo_status(&ws, name);
+            if repo.exists {
+                let commit = repo.last_commit.unwrap_or_else(|| "unknown".to_string());
+                let dirty = if repo.dirty { " *" } else { "" };
+                println!(
+                    "  {} {}{}: {}",
+                    "[OK]".green(),
+                    name,
+                    dirty.yellow(),
+                    commit.dimmed()
+                );
+            } else {
+                println!("  {} {}: {}", "[X]".red(), name, "missing".red());
+            }
+        }
+
+        // Check build
+        let build_dir = ws.join("build");
+        if build_dir.exists() {
+            println!();
+            if workspace_host_exe(&ws).exists() {
+                println!("{} Built: {}", "[OK]".green(), "yes".green());
+            } else {
+                println!("{} Built: {}", "->".yellow(), "not yet".yellow());
+            }
+        } else {
+            println!();
+            println!("{} Built: {}", "[X]".red(), "no".red());
+        }
+    } else {
+        println!("{} Not in an MPF workspace", "[X]".red());
+        println!();
+        println!("Run {} to create one.", "mpf-dev workspace init".cyan());
+    }
+
+    Ok(())
+}
+
+/// Whether a branch exists locally or on origin
+fn branch_exists(repo_dir: &Path, branch: &str) -> bool {
+    [
+        format!("refs/heads/{}", branch),
+        format!("refs/remotes/origin/{}", branch),
+    ]
+    .iter()
+    .any(|r| git_output(repo_dir, &["rev-parse", "--verify", "--quiet", r]).is_some())
+}
+
+/// Workspace checkout: switch all repos to the same branch
+pub fn workspace_checkout(branch: &str, create: bool) -> Result<()> {
+    let workspace = find_workspace_root()
+        .context("Not in an MPF workspace. Run 'mpf-dev workspace init' first.")?;
+
+    println!("{} Checking out '{}' in all repos...", "->".cyan(), branch);
+
+    let marker = WorkspaceMarker::load(&workspace);
+    let (mut succeeded, mut skipped, mut failed) = (0u32, 0u32, 0u32);
+    for name in marker.repos() {
+        let repo_dir = workspace.join(name);
+        if !repo_dir.exists() {
+            continue;
+        }
+
+        let exists = branch_exists(&repo_dir, branch);
+        let args: Vec<&str> = if exists {
+            vec!["checkout", branch]
+        } else if create {
+            vec!["checkout", "-b", branch]
+        } else {
+            println!(
+                "  {} {}: branch '{}' not found, skipped",
+                "⚠".yellow(),
+                name,
+                branch
+            );
+            skipped += 1;
+            continue;
+        };
+
+        let output = Command::new("git")
+            .current_dir(&repo_dir)
+            .args(&args)
+            .output()
+            .context("Failed to run git checkout")?;
+
+        if output.status.success() {
+            println!("  {} {}", "✓".green(), name);
+            succeeded += 1;
+        } else {
+            let err = String::from_utf8_lossy(&output.stderr);
+            println!("  {} {} — {}", "✗".red(), name, err.trim());
+            failed += 1;
+        }
+    }
+
+    println!();
+    println!(
+        "{} succeeded, {} skipped, {} failed",
+        succeeded.to_string().green(),
+        skipped.to_string().yellow(),
+        failed.to_string().red()
+    );
+
+    // Branches may carry different CMake configuration; refresh presets
+    reinit_all(&mut DevConfig::load().unwrap_or_default())?;
+
+    if failed > 0 {
+        bail!("Checkout failed in {} repo(s)", failed);
+    }
+    Ok(())
+}
+
+/// Recursively collect files under `dir`, skipping `.git` and `build*` directories
+/// unless `include_build` is set. Returned paths are relative to `base`.
+fn collect_files(
+    dir: &Path,
+    base: &Path,
+    include_build: bool,
+    out: &mut Vec<PathBuf>,
+) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if path.is_dir() {
+            if name == ".git" || (!include_build && name.starts_with("build")) {
+                continue;
+            }
+            collect_files(&path, base, include_build, out)?;
+        } else if let Ok(rel) = path.strip_prefix(base) {
+            out.push(rel.to_path_buf());
+        }
+    }
+    Ok(())
+}
+
+/// Files of a repo that git would consider part of the working tree
+/// (tracked + untracked, minus .gitignore'd paths)
+fn repo_files(workspace: &Path, name: &str) -> Result<Vec<PathBuf>> {
+    let repo_dir = workspace.join(name);
+    let listed = git_output(
+        &repo_dir,
+        &["ls-files", "--cached", "--others", "--exclude-standard"],
+    );
+
+    let mut files = Vec::new();
+    match listed {
+        Some(list) => {
+            for line in list.lines().filter(|l| !l.is_empty()) {
+                let rel = Path::new(name).join(line);
+                // ls-files --cached still lists tracked files deleted from disk
+                if workspace.join(&rel).is_file() {
+                    files.push(rel);
+                }
+            }
+        }
+        // Not a git repo: plain directory walk
+        None => collect_files(&repo_dir, workspace, false, &mut files)?,
+    }
+    Ok(files)
+}
+
+/// Workspace export: archive the workspace into a zip file
+pub fn workspace_export(output_path: &Path, include_build: bool, repos_only: bool) -> Result<()> {
+    let workspace = find_workspace_root()
+        .context("Not in an MPF workspace. Run 'mpf-dev workspace init' first.")?;
+
+    println!("{} Collecting files...", "->".cyan());
+
+    let marker = WorkspaceMarker::load(&workspace);
+    let mut files: Vec<PathBuf> = Vec::new();
+    for name in marker.repos() {
+        if workspace.join(name).exists() {
+            files.extend(repo_files(&workspace, name)?);
+        }
+    }
+    if !repos_only {
+        for generated in [
+            ".mpf-workspace",
+            ".mpf-workspace.code-workspace",
+            "CMakeLists.txt",
+            "CMakePresets.json",
+        ] {
+            if workspace.join(generated).is_file() {
+                files.push(PathBuf::from(generated));
+            }
+        }
+    }
+    if include_build && workspace.join("build").is_dir() {
+        collect_files(&workspace.join("build"), &workspace, true, &mut files)?;
+    }
+
+    // Never archive the archive itself
+    let output_abs = env::current_dir()?.join(output_path);
+    files.retain(|f| workspace.join(f) != output_abs);
+
+    let file = fs::File::create(output_path)
+        .with_context(|| format!("Failed to create {}", output_path.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    let pb = ProgressBar::new(files.len() as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} {msg}")?
+            .progress_chars("#>-"),
+    );
+
+    for rel in &files {
+        let name = rel.to_string_lossy().replace('\\', "/");
+        pb.set_message(name.clone());
+        zip.start_file(name, options)?;
+        let mut src = fs::File::open(workspace.join(rel))?;
+        std::io::copy(&mut src, &mut zip)?;
+        pb.inc(1);
+    }
+    zip.finish()?;
+    pb.finish_and_clear();
+
+    println!(
+        "{} Exported {} files to {}",
+        "[OK]".green(),
+        files.len(),
+        output_path.display()
+    );
+    Ok(())
+}
+
+/// Workspace sync: check out each repo at the commit recorded for the active SDK release.
+///
+/// The release is expected to carry a `workspace-manifest.json` asset mapping
+/// repo names to git SHAs, either flat or under a `components` key.
+pub async fn workspace_sync() -> Result<()> {
+    let workspace = find_workspace_root()
+        .context("Not in an MPF workspace. Run 'mpf-dev workspace init' first.")?;
+
+    let version = DevConfig::load()
+        .unwrap_or_default()
+        .sdk_version
+        .or_else(config::current_version)
+        .context("No SDK version set. Run 'mpf-dev setup' first.")?;
+
+    println!(
+        "{} Syncing workspace to SDK release {}...",
+        "->".cyan(),
+        version.green()
+    );
+
+    let token = github_token(&DevConfig::load().unwrap_or_default());
+    let release = fetch_release(Some(&version), token.as_deref()).await?;
+    let client = reqwest::Client::new();
+    let request = release["assets"]
+        .as_array()
+        .and_then(|assets| {
+            assets
+                .iter()
+                .find(|a| a["name"].as_str() == Some(MANIFEST_ASSET))
+        })
+        .and_then(|a| asset_request(&client, a, token.as_deref()))
+        .with_context(|| format!("Release {} has no {} asset", version, MANIFEST_ASSET))?;
+
+    let manifest: serde_json::Value = request
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await
+        .with_context(|| format!("Failed to parse {}", MANIFEST_ASSET))?;
+    let shas = manifest
+        .get("components")
+        .unwrap_or(&manifest)
+        .as_object()
+        .with_context(|| format!("{} is not a JSON object", MANIFEST_ASSET))?;
+
+    let marker = WorkspaceMarker::load(&workspace);
+    let (mut synced, mut failed) = (0u32, 0u32);
+    for name in marker.repos() {
+        let repo_dir = workspace.join(name);
+        let sha = match shas.get(name).and_then(|v| v.as_str()) {
+            Some(sha) => sha,
+            None => {
+                println!("  {} {}: not in manifest, skipped", "⚠".yellow(), name);
+                continue;
+            }
+        };
+        if !repo_dir.exists() {
+            println!("  {} {}: missing, skipped", "⚠".yellow(), name);
+            continue;
+        }
+
+        // Make sure the commit is available locally before checking it out
+        let _ = git_output(&repo_dir, &["fetch", "--quiet", "origin"]);
+        let output = Command::new("git")
+            .current_dir(&repo_dir)
+            .args(["checkout", "--quiet", sha])
+            .output()
+            .context("Failed to run git checkout")?;
+
+        if output.status.success() {
+            let short = &sha[..sha.len().min(10)];
+            println!("  {} {} @ {}", "✓".green(), name, short.dimmed());
+            synced += 1;
+        } else {
+            let err = String::from_utf8_lossy(&output.stderr);
+            println!("  {} {} — {}", "✗".red(), name, err.trim());
+            failed += 1;
+        }
+    }
+
+    println!();
+    println!("{} synced, {} failed", synced.to_string().green(), failed.to_string().red());
+    if failed > 0 {
+        bail!("Sync failed in {} repo(s)", failed);
+    }
+    Ok(())
+}
+
+/// Output longer than this many lines is sent through a pager on a tty
+const PAGER_THRESHOLD_LINES: usize = 60;
+
+/// Show text through $PAGER (default `less -R`), falling back to stdout
+fn page_output(text: &str) {
+    let pager = env::var("PAGER").unwrap_or_else(|_| "less -R".to_string());
+    let mut parts = pager.split_whitespace();
+    let spawned = parts.next().and_then(|program| {
+        Command::new(program)
+            .args(parts)
+            .stdin(std::process::Stdio::piped())
+            .spawn()
+            .ok()
+    });
+
+    match spawned {
+        Some(mut child) => {
+            if let Some(mut stdin) = child.stdin.take() {
+                // The user may quit the pager early; a broken pipe is fine
+                let _ = stdin.write_all(text.as_bytes());
+            }
+            let _ = child.wait();
+        }
+        None => print!("{}", text),
+    }
+}
+
+/// Workspace diff: show uncommitted changes across all repos
+pub fn workspace_diff(stat: bool) -> Result<()> {
+    let workspace = find_workspace_root()
+        .context("Not in an MPF workspace. Run 'mpf-dev workspace init' first.")?;
+
+    let is_tty = std::io::stdout().is_terminal();
+    let color = if is_tty { "--color=always" } else { "--color=never" };
+
+    let marker = WorkspaceMarker::load(&workspace);
+    let mut out = String::new();
+    for name in marker.repos() {
+        let repo_dir = workspace.join(name);
+        if !repo_dir.exists() {
+            continue;
+        }
+
+        // Diffing against HEAD covers both staged and unstaged changes
+        let mut args = vec!["diff", color];
+        if stat {
+            args.push("--stat");
+        }
+        args.push("HEAD");
+
+        let output = Command::new("git")
+            .current_dir(&repo_dir)
+            .args(&args)
+            .output()
+            .with_context(|| format!("Failed to run git diff in {}", name))?;
+        let diff = String::from_utf8_lossy(&output.stdout);
+
+        out.push_str(&format!("{}\n", format!("══ {} ══", name).bold().cyan()));
+        if diff.trim().is_empty() {
+            out.push_str(&format!("  {}\n", "clean".dimmed()));
+        } else {
+            out.push_str(&diff);
+            if !diff.ends_with('\n') {
+                out.push('\n');
+            }
+        }
+        out.push('\n');
+    }
+
+    if is_tty && out.lines().count() > PAGER_THRESHOLD_LINES {
+        page_output(&out);
+    } else {
+        print!("{}", out);
+    }
+
+    Ok(())
+}
+
+/// A clang tool (clang-tidy, clang-format) from PATH, or the one bundled
+/// with Qt Creator / Qt's LLVM tools
+fn find_clang_tool(tool: &str) -> Option<PathBuf> {
+    if tool_version(tool).is_some() {
+        return Some(PathBuf::from(tool));
+    }
+    let exe = if cfg!(windows) {
+        format!("{}.exe", tool)
+    } else {
+        tool.to_string()
+    };
+    // Qt path is <root>/<version>/<arch>; the tools live in <root>/Tools
+    let qt = PathBuf::from(detect_qt_path()?);
+    let tools = qt.parent()?.parent()?.join("Tools");
+    let mut candidates = vec![tools
+        .join("QtCreator")
+        .join("libexec")
+        .join("qtcreator")
+        .join("clang")
+        .join("bin")
+        .join(&exe)];
+    if let Ok(entries) = fs::read_dir(&tools) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            if entry.file_name().to_string_lossy().starts_with("llvm") {
+                candidates.push(entry.path().join("bin").join(&exe));
+            }
+        }
+    }
+    candidates.into_iter().find(|p| p.exists())
+}
+
+/// clang-tidy findings for one workspace repo
+struct LintResult {
+    repo: String,
+    files: usize,
+    warnings: usize,
+    errors: usize,
+}
+
+/// Workspace lint: run clang-tidy over every repo's compiled sources
+pub fn workspace_lint(fix: bool, filter: Option<&str>) -> Result<()> {
+    let workspace = find_workspace_root()
+        .context("Not in an MPF workspace. Run 'mpf-dev workspace init' first.")?;
+
+    let compile_commands = workspace.join("build").join("compile_commands.json");
+    if !compile_commands.exists() {
+        bail!(
+            "{} not found. Run 'mpf-dev workspace build' first \
+             (the workspace exports compile commands on configure).",
+            compile_commands.display()
+        );
+    }
+    let clang_tidy = find_clang_tool("clang-tidy")
+        .context("clang-tidy not found in PATH or the Qt Tools directory")?;
+
+    // The sources to lint are the ones the build actually compiles
+    let content = fs::read_to_string(&compile_commands)?;
+    let entries: Vec<serde_json::Value> = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", compile_commands.display()))?;
+    let mut sources: Vec<PathBuf> = entries
+        .iter()
+        .filter_map(|e| {
+            let file = PathBuf::from(e["file"].as_str()?);
+            Some(match e["directory"].as_str() {
+                Some(dir) if file.is_relative() => Path::new(dir).join(file),
+                _ => file,
+            })
+        })
+        .collect();
+    sources.sort();
+    sources.dedup();
+
+    let marker = WorkspaceMarker::load(&workspace);
+
+    println!("{}", "Linting MPF Workspace".bold().cyan());
+    println!("clang-tidy: {}", clang_tidy.display());
+    println!();
+
+    let mut results = Vec::new();
+    for repo in marker.repos() {
+        let repo_dir = workspace.join(repo);
+        let files: Vec<&PathBuf> = sources
+            .iter()
+            .filter(|f| f.starts_with(&repo_dir))
+            .filter(|f| {
+                filter.is_none_or(|pattern| {
+                    let rel = f.strip_prefix(&workspace).unwrap_or(f);
+                    rel.to_string_lossy().contains(pattern)
+                })
+            })
+            .collect();
+        if files.is_empty() {
             continue;
         }
 
-        println!("{} Cloning {}...", "->".cyan(), name);
-        let status = Command::new("git")
-            .args(["clone", url, &repo_dir.to_string_lossy()])
-            .status()
-            .context("Failed to run git clone")?;
-
-        if !status.success() {
-            bail!("Failed to clone {}", name);
+        println!("{} Linting {} ({} files)...", "->".cyan(), repo, files.len());
+        let mut tidy = Command::new(&clang_tidy);
+        tidy.current_dir(&workspace).args(["-p", "build", "--quiet"]);
+        if fix {
+            tidy.arg("--fix-errors");
+        }
+        let output = tidy
+            .args(&files)
+            .output()
+            .with_context(|| format!("Failed to run {}", clang_tidy.display()))?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut result = LintResult {
+            repo: repo.to_string(),
+            files: files.len(),
+            warnings: 0,
+            errors: 0,
+        };
+        for line in stdout.lines() {
+            if line.contains(": error:") {
+                result.errors += 1;
+            } else if line.contains(": warning:") {
+                result.warnings += 1;
+            }
+            println!("{}", line);
         }
+        results.push(result);
     }
 
-    // Create top-level CMakeLists.txt
-    let cmake_content = generate_workspace_cmake();
-    fs::write(workspace_dir.join("CMakeLists.txt"), cmake_content)?;
-
-    // Create CMakePresets.json for easy Qt Creator integration
-    let presets_content = generate_cmake_presets();
-    fs::write(workspace_dir.join("CMakePresets.json"), presets_content)?;
+    if results.is_empty() {
+        println!("{} No matching sources in compile_commands.json", "Note:".yellow());
+        return Ok(());
+    }
 
     println!();
-    println!("{} Workspace initialized!", "[OK]".green());
-    println!();
-    println!("Next steps:");
-    println!(
-        "  1. Open {} in Qt Creator",
-        workspace_dir.join("CMakeLists.txt").display()
-    );
-    println!("  2. Configure with MinGW kit");
-    println!("  3. Build and run");
-    println!();
-    println!("Or use CLI:");
-    println!("  cd {}", workspace_dir.display());
-    println!("  mpf-dev workspace build");
-    println!("  mpf-dev workspace run");
+    println!("{}", "Lint summary:".bold());
+    println!("  {:<24} {:>6} {:>9} {:>7}", "COMPONENT", "FILES", "WARNINGS", "ERRORS");
+    for r in &results {
+        let errors = if r.errors > 0 {
+            r.errors.to_string().red()
+        } else {
+            r.errors.to_string().green()
+        };
+        println!(
+            "  {:<24} {:>6} {:>9} {:>7}",
+            r.repo,
+            r.files,
+            r.warnings.to_string().yellow(),
+            errors
+        );
+    }
 
+    let errors: usize = results.iter().map(|r| r.errors).sum();
+    if errors > 0 {
+        bail!("clang-tidy reported {} error(s)", errors);
+    }
+    println!();
+    println!("{} No clang-tidy errors", "[OK]".green());
     Ok(())
 }
 
-/// Workspace build: build all components
-pub fn workspace_build(config: &str) -> Result<()> {
+/// Default style written to the workspace root when it has no .clang-format
+const DEFAULT_CLANG_FORMAT: &str = "# Generated by mpf-dev workspace format
+BasedOnStyle: LLVM
+IndentWidth: 4
+AccessModifierOffset: -4
+ColumnLimit: 100
+PointerAlignment: Right
+";
+
+/// C++ sources and headers under each repo's src/ and include/ directories
+fn format_sources(workspace: &Path, marker: &WorkspaceMarker) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for repo in marker.repos() {
+        for sub in ["src", "include"] {
+            let dir = workspace.join(repo).join(sub);
+            if dir.is_dir() {
+                collect_files(&dir, workspace, false, &mut files)?;
+            }
+        }
+    }
+    files.retain(|f| {
+        matches!(
+            f.extension().and_then(|e| e.to_str()),
+            Some("cpp") | Some("h") | Some("hpp")
+        )
+    });
+    files.sort();
+    Ok(files)
+}
+
+/// Workspace format: run clang-format over all component sources
+pub fn workspace_format(check: bool) -> Result<()> {
     let workspace = find_workspace_root()
         .context("Not in an MPF workspace. Run 'mpf-dev workspace init' first.")?;
+    let clang_format = find_clang_tool("clang-format")
+        .context("clang-format not found in PATH or the Qt Tools directory")?;
+
+    // Repos with their own .clang-format keep using it
+    let style_path = workspace.join(".clang-format");
+    if !style_path.exists() {
+        fs::write(&style_path, DEFAULT_CLANG_FORMAT)?;
+        println!("{} Generated {}", "->".cyan(), style_path.display());
+    }
 
-    println!("{}", "Building MPF Workspace".bold().cyan());
-    println!("Directory: {}", workspace.display());
-    println!("Configuration: {}", config);
-    println!();
-
-    let build_dir = workspace.join("build");
-
-    // Configure if needed
-    if !build_dir.join("CMakeCache.txt").exists() {
-        println!("{} Configuring CMake...", "->".cyan());
+    let marker = WorkspaceMarker::load(&workspace);
+    let files = format_sources(&workspace, &marker)?;
+    if files.is_empty() {
+        println!("{} No C++ sources found", "Note:".yellow());
+        return Ok(());
+    }
 
-        let status = Command::new("cmake")
-            .current_dir(&workspace)
-            .args([
-                "-B",
-                "build",
-                "-G",
-                "Ninja",
-                &format!("-DCMAKE_BUILD_TYPE={}", config),
-            ])
+    let mut format = Command::new(&clang_format);
+    format.current_dir(&workspace);
+    if check {
+        format.args(["--dry-run", "--Werror"]);
+    } else {
+        format.arg("-i");
+    }
+    format.args(&files);
+
+    let changed: Vec<&PathBuf> = if check {
+        // Each offending line is reported as `<file>:<line>:<col>: error: ...`
+        let output = format
+            .output()
+            .with_context(|| format!("Failed to run {}", clang_format.display()))?;
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        files
+            .iter()
+            .filter(|f| {
+                let prefix = format!("{}:", f.display());
+                stderr.lines().any(|l| l.starts_with(&prefix))
+            })
+            .collect()
+    } else {
+        let before: Vec<Vec<u8>> = files
+            .iter()
+            .map(|f| fs::read(workspace.join(f)).unwrap_or_default())
+            .collect();
+        let status = format
             .status()
-            .context("Failed to run cmake configure")?;
-
+            .with_context(|| format!("Failed to run {}", clang_format.display()))?;
         if !status.success() {
-            bail!("CMake configuration failed");
+            bail!("clang-format failed");
         }
-    }
-
-    // Build
-    println!("{} Building...", "->".cyan());
-
-    let status = Command::new("cmake")
-        .current_dir(&workspace)
-        .args(["--build", "build", "-j"])
-        .status()
-        .context("Failed to run cmake build")?;
+        files
+            .iter()
+            .zip(&before)
+            .filter(|(f, old)| fs::read(workspace.join(f)).unwrap_or_default() != **old)
+            .map(|(f, _)| f)
+            .collect()
+    };
 
-    if !status.success() {
-        bail!("Build failed");
+    for file in &changed {
+        let label = if check { "needs formatting" } else { "formatted" };
+        println!("  {} {} ({})", "~".yellow(), file.display(), label);
     }
-
-    println!();
-    println!("{} Build complete!", "[OK]".green());
     println!();
-    println!("Output:");
-    let host_name = if cfg!(windows) {
-        "mpf-host.exe"
-    } else {
-        "mpf-host"
-    };
-    println!(
-        "  Host: {}",
-        build_dir.join("bin").join(host_name).display()
-    );
-    println!("  Plugins: {}", build_dir.join("plugins").display());
-    println!("  QML: {}", build_dir.join("qml").display());
 
+    if check {
+        if !changed.is_empty() {
+            bail!("{} of {} file(s) need formatting", changed.len(), files.len());
+        }
+        println!("{} All {} file(s) are formatted", "[OK]".green(), files.len());
+    } else {
+        println!(
+            "{} Formatted {} of {} file(s)",
+            "[OK]".green(),
+            changed.len(),
+            files.len()
+        );
+    }
     Ok(())
 }
 
-/// Workspace run: run mpf-host from workspace
-pub fn workspace_run(args: Vec<String>) -> Result<()> {
-    let workspace = find_workspace_root()
-        .context("Not in an MPF workspace. Run 'mpf-dev workspace init' first.")?;
+/// Line coverage of one workspace repo
+struct CoverageResult {
+    repo: String,
+    covered: u64,
+    total: u64,
+}
 
-    let build_dir = workspace.join("build");
-    let host_exe = if cfg!(windows) {
-        build_dir.join("bin").join("mpf-host.exe")
+fn percent(covered: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
     } else {
-        build_dir.join("bin").join("mpf-host")
-    };
-
-    if !host_exe.exists() {
-        bail!("mpf-host not found. Run 'mpf-dev workspace build' first.");
+        covered as f64 * 100.0 / total as f64
     }
+}
 
-    println!("{} Running mpf-host from workspace...", "->".cyan());
+/// Workspace coverage: run the tests of a coverage build and report line
+/// coverage per component with gcovr
+pub fn workspace_coverage(html: bool, threshold: Option<u8>) -> Result<()> {
+    let workspace = find_workspace_root()
+        .context("Not in an MPF workspace. Run 'mpf-dev workspace init' first.")?;
+    let build_dir = workspace.join("build");
 
-    let mut cmd = Command::new(&host_exe);
-    cmd.current_dir(&workspace);
-    cmd.args(&args);
+    if cache_has_coverage(&build_dir) != Some(true) {
+        bail!("The workspace is not a coverage build. Run 'mpf-dev workspace build --coverage' first.");
+    }
+    if tool_version("gcovr").is_none() {
+        bail!("gcovr not found. Install it with `pip install gcovr`.");
+    }
 
-    // Set library paths
-    #[cfg(windows)]
+    // Reset counters from earlier runs so the report reflects this test run
+    let mut build_files = Vec::new();
+    collect_files(&build_dir, &build_dir, true, &mut build_files)?;
+    for file in build_files
+        .iter()
+        .filter(|f| f.extension().is_some_and(|e| e == "gcda"))
     {
-        let current_path = env::var("PATH").unwrap_or_default();
-        let lib_path = format!(
-            "{};{};{}",
-            build_dir.join("bin").display(),
-            build_dir.join("plugins").display(),
-            current_path
-        );
-        cmd.env("PATH", lib_path);
+        fs::remove_file(build_dir.join(file))?;
     }
 
-    #[cfg(unix)]
-    {
-        let lib_path = format!(
-            "{}:{}",
-            build_dir.join("bin").display(),
-            build_dir.join("plugins").display()
+    println!("{} Running tests...", "->".cyan());
+    let status = Command::new("ctest")
+        .current_dir(&workspace)
+        .args(["--test-dir", "build", "--output-on-failure"])
+        .status()
+        .context("Failed to run ctest")?;
+    if !status.success() {
+        println!(
+            "{} Some tests failed; coverage reflects the tests that ran",
+            "Warning:".yellow()
         );
-        cmd.env("LD_LIBRARY_PATH", lib_path);
     }
 
-    cmd.env(
-        "QML_IMPORT_PATH",
-        build_dir.join("qml").to_string_lossy().to_string(),
-    );
-
-    let status = cmd.status()?;
-    std::process::exit(status.code().unwrap_or(1));
-}
-
-/// Workspace status: show workspace info
-pub fn workspace_status() -> Result<()> {
-    let workspace = find_workspace_root();
-
-    println!("{}", "MPF Workspace Status".bold().cyan());
-    println!();
+    let coverage_dir = build_dir.join("coverage");
+    fs::create_dir_all(&coverage_dir)?;
+    let summary_path = coverage_dir.join("summary.json");
 
-    if let Some(ws) = workspace {
-        println!("{} Workspace: {}", "[OK]".green(), ws.display());
+    println!("{} Collecting coverage...", "->".cyan());
+    let mut gcovr = Command::new("gcovr");
+    gcovr
+        .current_dir(&workspace)
+        .args(["--root", ".", "--exclude", "build/"])
+        .arg("--json-summary")
+        .arg(&summary_path);
+    // Clang writes LLVM-flavoured gcov data
+    if cmake_cache_value(&build_dir, "CMAKE_CXX_COMPILER_ID").is_some_and(|id| id.contains("Clang")) {
+        gcovr.args(["--gcov-executable", "llvm-cov gcov"]);
+    }
+    if html {
+        gcovr.arg("--html-details").arg(coverage_dir.join("index.html"));
+    }
+    let status = gcovr.arg("build").status().context("Failed to run gcovr")?;
+    if !status.success() {
+        bail!("gcovr failed");
+    }
 
-        // Check each component
-        for (name, _) in WORKSPACE_REPOS {
-            let repo_dir = ws.join(name);
-            if repo_dir.exists() {
-                let output = Command::new("git")
-                    .current_dir(&repo_dir)
-                    .args(["log", "-1", "--oneline"])
-                    .output();
-
-                let commit = output
-                    .ok()
-                    .and_then(|o| String::from_utf8(o.stdout).ok())
-                    .map(|s| s.trim().to_string())
-                    .unwrap_or_else(|| "unknown".to_string());
-
-                println!("  {} {}: {}", "[OK]".green(), name, commit.dimmed());
-            } else {
-                println!("  {} {}: {}", "[X]".red(), name, "missing".red());
-            }
+    let summary: serde_json::Value = serde_json::from_str(&fs::read_to_string(&summary_path)?)
+        .with_context(|| format!("Failed to parse {}", summary_path.display()))?;
+    let marker = WorkspaceMarker::load(&workspace);
+    let mut results: Vec<CoverageResult> = marker
+        .repos()
+        .into_iter()
+        .map(|repo| CoverageResult {
+            repo: repo.to_string(),
+            covered: 0,
+            total: 0,
+        })
+        .collect();
+    for file in summary["files"].as_array().into_iter().flatten() {
+        let name = file["filename"].as_str().unwrap_or_default().replace('\\', "/");
+        let repo = name.split('/').next().unwrap_or_default();
+        if let Some(result) = results.iter_mut().find(|r| r.repo == repo) {
+            result.covered += file["line_covered"].as_u64().unwrap_or(0);
+            result.total += file["line_total"].as_u64().unwrap_or(0);
         }
+    }
+    results.retain(|r| r.total > 0);
 
-        // Check build
-        let build_dir = ws.join("build");
-        if build_dir.exists() {
-            println!();
-            let host_exe = if cfg!(windows) {
-                build_dir.join("bin").join("mpf-host.exe")
-            } else {
-                build_dir.join("bin").join("mpf-host")
-            };
+    println!();
+    println!("{}", "Coverage summary:".bold());
+    println!("  {:<24} {:>15} {:>9}", "COMPONENT", "LINES", "COVERAGE");
+    for r in &results {
+        println!(
+            "  {:<24} {:>15} {:>8.1}%",
+            r.repo,
+            format!("{}/{}", r.covered, r.total),
+            percent(r.covered, r.total)
+        );
+    }
+    let covered: u64 = results.iter().map(|r| r.covered).sum();
+    let total: u64 = results.iter().map(|r| r.total).sum();
+    let total_percent = percent(covered, total);
+    println!(
+        "  {:<24} {:>15} {:>8.1}%",
+        "Total".bold(),
+        format!("{}/{}", covered, total),
+        total_percent
+    );
+    if html {
+        println!();
+        println!("HTML report: {}", coverage_dir.join("index.html").display());
+    }
 
-            if host_exe.exists() {
-                println!("{} Built: {}", "[OK]".green(), "yes".green());
-            } else {
-                println!("{} Built: {}", "->".yellow(), "not yet".yellow());
-            }
-        } else {
-            println!();
-            println!("{} Built: {}", "[X]".red(), "no".red());
+    if let Some(threshold) = threshold {
+        if total_percent < f64::from(threshold) {
+            bail!(
+                "Total coverage {:.1}% is below the {}% threshold",
+                total_percent,
+                threshold
+            );
         }
-    } else {
-        println!("{} Not in an MPF workspace", "[X]".red());
         println!();
-        println!("Run {} to create one.", "mpf-dev workspace init".cyan());
+        println!(
+            "{} Coverage {:.1}% meets the {}% threshold",
+            "[OK]".green(),
+            total_percent,
+            threshold
+        );
     }
-
     Ok(())
 }
 
 // ─── CMake template generators ───────────────────────────────────────────────
 
-fn generate_workspace_cmake() -> String {
-    String::from(
-        r##"cmake_minimum_required(VERSION 3.21)
+/// Top of the workspace CMakeLists.txt, before any component
+const CMAKE_PRELUDE: &str = r##"cmake_minimum_required(VERSION 3.21)
 project(mpf-workspace VERSION 1.0.0 LANGUAGES CXX)
 
 set(CMAKE_CXX_STANDARD 17)
 set(CMAKE_CXX_STANDARD_REQUIRED ON)
 set(CMAKE_AUTOMOC ON)
+set(CMAKE_EXPORT_COMPILE_COMMANDS ON)
 
 if(COMMAND qt_policy)
     qt_policy(SET QTP0001 NEW)
@@ -298,7 +2076,13 @@ endif()
 
 find_package(Qt6 REQUIRED COMPONENTS Core Gui Qml Quick QuickControls2 Network)
 
-# SDK (header-only)
+"##;
+
+/// CMake block for each built-in repo, in dependency order
+const CMAKE_SECTIONS: &[(&str, &str)] = &[
+    (
+        "mpf-sdk",
+        r##"# SDK (header-only)
 add_library(mpf-sdk INTERFACE)
 add_library(MPF::sdk ALIAS mpf-sdk)
 target_include_directories(mpf-sdk INTERFACE
@@ -306,7 +2090,11 @@ target_include_directories(mpf-sdk INTERFACE
 )
 target_link_libraries(mpf-sdk INTERFACE Qt6::Core Qt6::Gui Qt6::Qml)
 
-# HTTP Client (static)
+"##,
+    ),
+    (
+        "mpf-http-client",
+        r##"# HTTP Client (static)
 add_library(mpf-http-client STATIC
     mpf-http-client/src/http_client.cpp
     mpf-http-client/include/mpf/http/http_client.h
@@ -318,7 +2106,11 @@ target_include_directories(mpf-http-client PUBLIC
 target_compile_definitions(mpf-http-client PUBLIC MPF_HTTP_CLIENT_STATIC)
 target_link_libraries(mpf-http-client PUBLIC Qt6::Core Qt6::Network)
 
-# UI Components
+"##,
+    ),
+    (
+        "mpf-ui-components",
+        r##"# UI Components
 add_compile_definitions(MPF_UI_COMPONENTS_EXPORTS)
 
 set(UI_QML_FILES
@@ -355,7 +2147,11 @@ target_include_directories(mpf-ui-components PUBLIC
 )
 target_link_libraries(mpf-ui-components PUBLIC Qt6::Core Qt6::Gui Qt6::Qml Qt6::Quick)
 
-# Host Application
+"##,
+    ),
+    (
+        "mpf-host",
+        r##"# Host Application
 add_executable(mpf-host
     mpf-host/src/main.cpp
     mpf-host/src/application.cpp
@@ -383,10 +2179,10 @@ target_link_libraries(mpf-host PRIVATE
 # Generate version header
 file(WRITE ${CMAKE_CURRENT_BINARY_DIR}/host/mpf/version.h [=[
 #pragma once
-#define MPF_VERSION_MAJOR 1
-#define MPF_VERSION_MINOR 0
-#define MPF_VERSION_PATCH 0
-#define MPF_VERSION_STRING "1.0.0-workspace"
+#define MPF_VERSION_MAJOR @MPF_VERSION_MAJOR@
+#define MPF_VERSION_MINOR @MPF_VERSION_MINOR@
+#define MPF_VERSION_PATCH @MPF_VERSION_PATCH@
+#define MPF_VERSION_STRING "@MPF_VERSION_STRING@"
 ]=])
 
 # Generate sdk_paths header
@@ -420,7 +2216,11 @@ qt_add_qml_module(mpf-host
     OUTPUT_DIRECTORY ${CMAKE_BINARY_DIR}/qml/MPF/Host
 )
 
-# Orders Plugin
+"##,
+    ),
+    (
+        "mpf-plugin-orders",
+        r##"# Orders Plugin
 add_library(orders-plugin SHARED
     mpf-plugin-orders/src/orders_plugin.cpp
     mpf-plugin-orders/src/orders_service.cpp
@@ -453,7 +2253,11 @@ qt_add_qml_module(orders-plugin
     NO_PLUGIN
 )
 
-# Rules Plugin
+"##,
+    ),
+    (
+        "mpf-plugin-rules",
+        r##"# Rules Plugin
 add_library(rules-plugin SHARED
     mpf-plugin-rules/src/rules_plugin.cpp
     mpf-plugin-rules/src/orders_service.cpp
@@ -487,49 +2291,254 @@ qt_add_qml_module(rules-plugin
     NO_PLUGIN
 )
 
-# Output directories
-set_target_properties(mpf-host PROPERTIES
-    RUNTIME_OUTPUT_DIRECTORY ${CMAKE_BINARY_DIR}/bin
-)
-set_target_properties(orders-plugin rules-plugin PROPERTIES
-    LIBRARY_OUTPUT_DIRECTORY ${CMAKE_BINARY_DIR}/plugins
-    RUNTIME_OUTPUT_DIRECTORY ${CMAKE_BINARY_DIR}/plugins
-)
-
-file(MAKE_DIRECTORY ${CMAKE_BINARY_DIR}/plugins)
-file(MAKE_DIRECTORY ${CMAKE_BINARY_DIR}/qml)
 "##,
-    )
+    ),
+];
+
+/// Version used for the generated headers when a repo declares none
+const FALLBACK_VERSION: &str = "0.0.0-workspace";
+
+/// Read `project(... VERSION X.Y.Z ...)` from a repo's top-level CMakeLists.txt
+fn extract_cmake_version(repo_dir: &Path) -> Option<String> {
+    static PROJECT_VERSION: OnceLock<Regex> = OnceLock::new();
+    let re = PROJECT_VERSION.get_or_init(|| {
+        Regex::new(r"(?is)\bproject\s*\([^)]*?\bVERSION\s+([0-9]+(?:\.[0-9]+){0,3})")
+            .expect("valid project() regex")
+    });
+    let content = fs::read_to_string(repo_dir.join("CMakeLists.txt")).ok()?;
+    re.captures(&content).map(|caps| caps[1].to_string())
 }
 
-fn generate_cmake_presets() -> String {
-    r##"{
-  "version": 6,
-  "configurePresets": [
-    {
-      "name": "debug",
-      "displayName": "Debug",
-      "generator": "Ninja",
-      "binaryDir": "${sourceDir}/build",
-      "cacheVariables": {
-        "CMAKE_BUILD_TYPE": "Debug"
-      }
-    },
-    {
-      "name": "release",
-      "displayName": "Release",
-      "generator": "Ninja",
-      "binaryDir": "${sourceDir}/build",
-      "cacheVariables": {
-        "CMAKE_BUILD_TYPE": "Release"
-      }
-    }
-  ],
-  "buildPresets": [
-    {"name": "debug", "configurePreset": "debug"},
-    {"name": "release", "configurePreset": "release"}
-  ]
-}
-"##
-    .to_string()
+/// Fill the `@MPF_VERSION_*@` placeholders of a section with the repo's version
+fn substitute_version(section: &str, version: Option<&str>) -> String {
+    let (string, numbers) = match version {
+        Some(v) => (v.to_string(), v),
+        None => (FALLBACK_VERSION.to_string(), "0.0.0"),
+    };
+    let mut parts = numbers.split('.').map(|p| p.parse::<u32>().unwrap_or(0));
+    section
+        .replace("@MPF_VERSION_MAJOR@", &parts.next().unwrap_or(0).to_string())
+        .replace("@MPF_VERSION_MINOR@", &parts.next().unwrap_or(0).to_string())
+        .replace("@MPF_VERSION_PATCH@", &parts.next().unwrap_or(0).to_string())
+        .replace("@MPF_VERSION_STRING@", &string)
+}
+
+fn generate_workspace_cmake(
+    workspace: &Path,
+    excluded: &[String],
+    custom: &[CustomRepo],
+) -> String {
+    let included = |repo: &str| !excluded.iter().any(|e| e == repo);
+
+    let mut cmake = String::from(CMAKE_PRELUDE);
+    for (repo, section) in CMAKE_SECTIONS {
+        if included(repo) {
+            let version = extract_cmake_version(&workspace.join(repo));
+            cmake.push_str(&substitute_version(section, version.as_deref()));
+        }
+    }
+
+    cmake.push_str("# Output directories\n");
+    if included("mpf-host") {
+        cmake.push_str(
+            "set_target_properties(mpf-host PROPERTIES\n    RUNTIME_OUTPUT_DIRECTORY ${CMAKE_BINARY_DIR}/bin\n)\n",
+        );
+    }
+    let plugins: Vec<&str> = [("mpf-plugin-orders", "orders-plugin"), ("mpf-plugin-rules", "rules-plugin")]
+        .iter()
+        .filter(|(repo, _)| included(repo))
+        .map(|(_, target)| *target)
+        .collect();
+    if !plugins.is_empty() {
+        cmake.push_str(&format!(
+            "set_target_properties({} PROPERTIES\n    LIBRARY_OUTPUT_DIRECTORY ${{CMAKE_BINARY_DIR}}/plugins\n    RUNTIME_OUTPUT_DIRECTORY ${{CMAKE_BINARY_DIR}}/plugins\n)\n",
+            plugins.join(" ")
+        ));
+    }
+    cmake.push_str(
+        "\nfile(MAKE_DIRECTORY ${CMAKE_BINARY_DIR}/plugins)\nfile(MAKE_DIRECTORY ${CMAKE_BINARY_DIR}/qml)\n",
+    );
+
+    // Install in the SDK layout (used by `workspace build --install`)
+    cmake.push_str("\n# Install into the SDK layout\n");
+    if included("mpf-host") {
+        cmake.push_str("install(TARGETS mpf-host RUNTIME DESTINATION bin)\n");
+    }
+    if !plugins.is_empty() {
+        cmake.push_str(&format!(
+            "install(TARGETS {} LIBRARY DESTINATION plugins RUNTIME DESTINATION plugins)\n",
+            plugins.join(" ")
+        ));
+    }
+    cmake.push_str("install(DIRECTORY ${CMAKE_BINARY_DIR}/qml/ DESTINATION qml)\n");
+
+    if !custom.is_empty() {
+        cmake.push_str("\n# Custom repositories (mpf-dev workspace add)\n");
+        for repo in custom {
+            cmake.push_str(&format!("add_subdirectory({})\n", repo.name));
+        }
+    }
+    cmake
 }
+
+fn generate_vscode_workspace(workspace_dir: &Path, marker: &WorkspaceMarker) -> Result<String> {
+    let mut folders = vec![serde_json::json!({ "name": "workspace", "path": "." })];
+    for name in marker.repos() {
+        folders.push(serde_json::json!({ "path": name }));
+    }
+
+    let sdk_current = config::current_link().to_string_lossy().replace('\\', "/");
+    let build_dir = workspace_dir.join("build").to_string_lossy().replace('\\', "/");
+
+    let mut include_path = vec!["${workspaceFolder}/include".to_string()];
+    include_path.push("${workspaceFolder}/src".to_string());
+    include_path.push(format!("{}/include", sdk_current));
+    if let Some(qt) = detect_qt_path() {
+        include_path.push(format!("{}/include/**", qt.replace('\\', "/")));
+    }
+
+    let workspace = serde_json::json!({
+        "folders": folders,
+        "settings": {
+            "cmake.configureOnOpen": false,
+            "cmake.buildDirectory": "${workspaceFolder}/build",
+            "C_Cpp.default.cppStandard": "c++17",
+            "C_Cpp.default.includePath": include_path,
+            "C_Cpp.default.compileCommands": format!("{}/compile_commands.json", build_dir),
+            "C_Cpp.default.configurationProvider": "ms-vscode.cmake-tools"
+        },
+        "extensions": {
+            "recommendations": [
+                "ms-vscode.cmake-tools",
+                "ms-vscode.cpptools"
+            ]
+        }
+    });
+
+    Ok(serde_json::to_string_pretty(&workspace)? + "\n")
+}
+
+/// Patterns in the generated workspace .gitignore
+const WORKSPACE_GITIGNORE: &[&str] = &[
+    "build/",
+    "build-release/",
+    "CMakeUserPresets.json",
+    ".clang-tidy",
+    ".vscode/",
+    "*.user",
+    "*.suo",
+    "Thumbs.db",
+    ".DS_Store",
+];
+
+/// Patterns every component repo should ignore on its own
+const COMPONENT_GITIGNORE: &[&str] = &["build/", "CMakeUserPresets.json"];
+
+fn generate_workspace_gitignore() -> String {
+    let mut content = String::from("# Generated by mpf-dev workspace init\n");
+    for pattern in WORKSPACE_GITIGNORE {
+        content.push_str(pattern);
+        content.push('\n');
+    }
+    content
+}
+
+/// Warn about component .gitignore files that miss or re-include the
+/// standard patterns. The workspace .gitignore doesn't apply inside the
+/// component repos, since each is its own git repository.
+fn check_component_gitignores(workspace: &Path, marker: &WorkspaceMarker) {
+    for repo in marker.repos() {
+        let repo_dir = workspace.join(repo);
+        if !repo_dir.exists() {
+            continue;
+        }
+        let content = fs::read_to_string(repo_dir.join(".gitignore")).unwrap_or_default();
+        let lines: Vec<&str> = content
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .collect();
+
+        let reincluded: Vec<&str> = lines
+            .iter()
+            .filter_map(|l| l.strip_prefix('!'))
+            .filter(|p| WORKSPACE_GITIGNORE.contains(p))
+            .collect();
+        let missing: Vec<&str> = COMPONENT_GITIGNORE
+            .iter()
+            .copied()
+            .filter(|p| {
+                let bare = p.trim_end_matches('/');
+                !lines.iter().any(|l| {
+                    let l = l.trim_start_matches('/').trim_end_matches('/');
+                    l == bare
+                })
+            })
+            .collect();
+
+        if !reincluded.is_empty() {
+            println!(
+                "{} {}/.gitignore re-includes {}",
+                "Note:".yellow(),
+                repo,
+                reincluded.join(", ")
+            );
+        }
+        if !missing.is_empty() {
+            println!(
+                "{} {}/.gitignore doesn't ignore {}",
+                "Note:".yellow(),
+                repo,
+                missing.join(", ")
+            );
+        }
+    }
+}
+
+fn generate_cmake_presets(generator: &str) -> String {
+    let multi_config = is_multi_config(generator);
+    let configure = |name: &str, display: &str, build_type: &str| {
+        let mut cache = serde_json::Map::new();
+        if !multi_config {
+            cache.insert("CMAKE_BUILD_TYPE".into(), build_type.into());
+        }
+        add_platform_cache_variables(&mut cache);
+        serde_json::json!({
+            "name": name,
+            "displayName": display,
+            "generator": generator,
+            "binaryDir": "${sourceDir}/build",
+            "cacheVariables": cache
+        })
+    };
+    let build = |name: &str, build_type: &str| {
+        let mut preset = serde_json::json!({"name": name, "configurePreset": name});
+        if multi_config {
+            preset["configuration"] = build_type.into();
+        }
+        preset
+    };
+
+    let presets = serde_json::json!({
+        "version": 6,
+        "configurePresets": [
+            configure("debug", "Debug", "Debug"),
+            configure("release", "Release", "Release")
+        ],
+        "buildPresets": [build("debug", "Debug"), build("release", "Release")]
+    });
+    let mut content = serde_json::to_string_pretty(&presets).unwrap_or_default();
+    content.push('\n');
+    content
+}
+
+/// Deployment target and architecture for macOS builds
+#[cfg(target_os = "macos")]
+fn add_platform_cache_variables(cache: &mut serde_json::Map<String, serde_json::Value>) {
+    let arch = if env::consts::ARCH == "aarch64" { "arm64" } else { "x86_64" };
+    cache.insert("CMAKE_OSX_DEPLOYMENT_TARGET".into(), "12.0".into());
+    cache.insert("CMAKE_OSX_ARCHITECTURES".into(), arch.into());
+}
+
+#[cfg(not(target_os = "macos"))]
+fn add_platform_cache_variables(_cache: &mut serde_json::Map<String, serde_json::Value>) {}
@@ -1,9 +1,15 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use colored::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
+pub mod migration;
+
+/// dev.json schema version written by this build (see `config::migration`)
+pub const CURRENT_SCHEMA_VERSION: u32 = 1;
+
 /// SDK root directory (~/.mpf-sdk)
 pub fn sdk_root() -> PathBuf {
     dirs::home_dir()
@@ -21,6 +27,17 @@ pub fn current_link() -> PathBuf {
     sdk_root().join("current")
 }
 
+/// Download cache for SDK archives (~/.mpf-sdk/cache)
+pub fn cache_dir() -> PathBuf {
+    sdk_root().join("cache")
+}
+
+/// Env var file loaded by `run` while an environment is active
+/// (~/.mpf-sdk/environments/<name>.env)
+pub fn environment_env_file(name: &str) -> PathBuf {
+    sdk_root().join("environments").join(format!("{}.env", name))
+}
+
 /// Path to a specific version directory
 pub fn version_dir(version: &str) -> PathBuf {
     sdk_root().join(version)
@@ -28,14 +45,87 @@ pub fn version_dir(version: &str) -> PathBuf {
 
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct DevConfig {
+    /// dev.json schema version; older files are migrated on load
+    #[serde(default)]
+    pub schema_version: u32,
+
     #[serde(default)]
     pub sdk_version: Option<String>,
     
     #[serde(default)]
     pub components: HashMap<String, ComponentConfig>,
+
+    /// Shell command run before any link writes dev.json
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_link_hook: Option<String>,
+
+    /// Shell command run after any link has been saved and propagated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_link_hook: Option<String>,
+
+    /// Extra CMake cache variables added to generated presets
+    /// (never override the variables mpf-dev computes itself)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub extra_cmake_vars: HashMap<String, String>,
+
+    /// Compiler launcher injected into generated presets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compiler_cache: Option<CompilerCache>,
+
+    /// Named SDK versions (`stable`, `nightly`, user-defined) usable in place
+    /// of a version in `setup` and `use`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub version_aliases: HashMap<String, String>,
+
+    /// GitHub token for API requests and downloads (stored in plaintext)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github_token: Option<String>,
+
+    /// When reinit_all last regenerated CMakeUserPresets.json (RFC 3339)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_reinit: Option<String>,
+
+    /// Named component overrides (e.g. dev/staging/prod); each entry replaces
+    /// the component of the same name while that environment is active
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub environments: HashMap<String, HashMap<String, ComponentConfig>>,
+
+    /// Environment whose overrides are applied, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_environment: Option<String>,
+
+    /// How many parent directories of a linked build path are searched for
+    /// the project root (default: 3)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_root_search_depth: Option<u32>,
+
+    /// File that marks a project root (default: any CMake, QMake or Meson project file)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_root_marker: Option<String>,
+
+    /// Directories scanned for mpf-plugin.json manifests on every `run`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub auto_discover_paths: Vec<String>,
+
+    /// Qt prefix found by the last detection (see `compiler_cache_expiry`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cached_qt_path: Option<String>,
+
+    /// C compiler found by the last detection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cached_gcc_path: Option<String>,
+
+    /// C++ compiler found by the last detection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cached_gpp_path: Option<String>,
+
+    /// Unix timestamp after which the cached Qt/compiler paths are re-detected
+    /// (unrelated to the ccache/sccache `compiler_cache` setting)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compiler_cache_expiry: Option<u64>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct ComponentConfig {
     pub mode: ComponentMode,
     
@@ -60,11 +150,116 @@ pub struct ComponentConfig {
     /// to regenerate CMakeUserPresets.json when dev.json changes.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub root: Option<String>,
+
+    /// Further project roots using this component (`mpf-dev add-root`),
+    /// regenerated by reinit_all together with `root`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roots: Vec<String>,
+
+    /// CMake package config directory, added to CMAKE_PREFIX_PATH as-is
+    /// for install trees whose layout can't be inferred from `lib`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cmake_config: Option<String>,
+
+    /// Per-component hook run before the global pre_link_hook
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_link_hook: Option<String>,
+
+    /// Per-component hook run after the global post_link_hook
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_link_hook: Option<String>,
+
+    /// Locked components are skipped by `unlink` unless --force is given
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub locked: bool,
+
+    /// Disabled components keep their paths but are left out of presets
+    /// and the run environment, as if unlinked
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub enabled: bool,
+
+    /// Components with a higher priority come first in the environment paths
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub priority: i32,
+
+    /// Command that builds this component in a workspace, used instead of
+    /// the default cmake invocation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_command: Option<String>,
+
+    /// When this component was last linked (RFC 3339)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub linked_at: Option<String>,
+
+    /// Output of `mpf-host --version` when the host was linked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detected_version: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+fn default_true() -> bool {
+    true
+}
+
+fn is_true(value: &bool) -> bool {
+    *value
+}
+
+fn is_zero(value: &i32) -> bool {
+    *value == 0
+}
+
+impl Default for ComponentConfig {
+    fn default() -> Self {
+        Self {
+            mode: ComponentMode::default(),
+            lib: None,
+            qml: None,
+            plugin: None,
+            headers: None,
+            bin: None,
+            root: None,
+            roots: Vec::new(),
+            cmake_config: None,
+            pre_link_hook: None,
+            post_link_hook: None,
+            locked: false,
+            enabled: true,
+            priority: 0,
+            build_command: None,
+            linked_at: None,
+            detected_version: None,
+        }
+    }
+}
+
+impl ComponentConfig {
+    /// Every project root of this component: `root`, then `roots`
+    pub fn all_roots(&self) -> impl Iterator<Item = &String> {
+        self.root.iter().chain(&self.roots)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CompilerCache {
+    Ccache,
+    Sccache,
+}
+
+impl CompilerCache {
+    /// Executable name, also used as the CMake compiler launcher
+    pub fn program(self) -> &'static str {
+        match self {
+            CompilerCache::Ccache => "ccache",
+            CompilerCache::Sccache => "sccache",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum ComponentMode {
+    #[default]
     Binary,
     Source,
 }
@@ -72,14 +267,31 @@ pub enum ComponentMode {
 impl DevConfig {
     pub fn load() -> Result<Self> {
         let path = dev_config_path();
-        if path.exists() {
-            let content = fs::read_to_string(&path)
-                .with_context(|| format!("Failed to read {}", path.display()))?;
-            serde_json::from_str(&content)
-                .with_context(|| "Failed to parse dev.json")
-        } else {
-            Ok(Self::default())
+        if !path.exists() {
+            return Ok(Self {
+                schema_version: CURRENT_SCHEMA_VERSION,
+                ..Self::default()
+            });
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let value: serde_json::Value =
+            serde_json::from_str(&content).with_context(|| "Failed to parse dev.json")?;
+        let version = migration::schema_version(&value);
+        if version >= CURRENT_SCHEMA_VERSION {
+            return serde_json::from_value(value).with_context(|| "Failed to parse dev.json");
         }
+
+        let config = migration::migrate_config(value, version)?;
+        config.save()?;
+        eprintln!(
+            "{} Migrated dev.json from schema version {} to {}",
+            "Note:".yellow(),
+            version,
+            CURRENT_SCHEMA_VERSION
+        );
+        Ok(config)
     }
     
     pub fn save(&self) -> Result<()> {
@@ -95,6 +307,86 @@ impl DevConfig {
             .with_context(|| format!("Failed to write {}", path.display()))?;
         Ok(())
     }
+
+    /// Parent directories searched by `infer_project_root`
+    pub fn project_root_search_depth(&self) -> u32 {
+        self.project_root_search_depth.unwrap_or(3)
+    }
+
+    /// File whose presence marks a project root, if one is configured
+    pub fn project_root_marker(&self) -> Option<&str> {
+        self.project_root_marker.as_deref()
+    }
+
+    /// Forget the cached Qt/compiler detection results
+    pub fn clear_detection_cache(&mut self) {
+        self.cached_qt_path = None;
+        self.cached_gcc_path = None;
+        self.cached_gpp_path = None;
+        self.compiler_cache_expiry = None;
+    }
+
+    /// Component overrides of the active environment
+    pub fn active_overrides(&self) -> Option<&HashMap<String, ComponentConfig>> {
+        self.environments.get(self.active_environment.as_ref()?)
+    }
+
+    /// Combine two configs, e.g. a global dev.json with a more specific one.
+    ///
+    /// `base` keeps its settings; `overlay` only fills in `sdk_version` when
+    /// `base` has none. Components and extra CMake variables from both are
+    /// kept, with `overlay` winning for keys present in both.
+    pub fn merge(base: DevConfig, overlay: DevConfig) -> DevConfig {
+        let mut merged = base;
+        if merged.sdk_version.is_none() {
+            merged.sdk_version = overlay.sdk_version;
+        }
+        merged.components.extend(overlay.components);
+        merged.extra_cmake_vars.extend(overlay.extra_cmake_vars);
+        merged
+    }
+
+    /// The components with the active environment's overrides merged in,
+    /// as `run` and the presets see them (other settings are left default)
+    pub fn with_active_overrides(&self) -> DevConfig {
+        let base = DevConfig {
+            components: self.components.clone(),
+            ..Default::default()
+        };
+        let overlay = DevConfig {
+            components: self.active_overrides().cloned().unwrap_or_default(),
+            ..Default::default()
+        };
+        DevConfig::merge(base, overlay)
+    }
+
+    /// Linked components that are not disabled, with the active
+    /// environment's overrides in place of the base entries. Highest
+    /// priority first, ties in name order, so generated paths are stable.
+    pub fn enabled_components(&self) -> Vec<(String, ComponentConfig)> {
+        let mut components: Vec<_> = self
+            .with_active_overrides()
+            .components
+            .into_iter()
+            .filter(|(_, c)| c.enabled)
+            .collect();
+        components.sort_by(|a, b| b.1.priority.cmp(&a.1.priority).then(a.0.cmp(&b.0)));
+        components
+    }
+
+    /// A component linked in source mode as `run` and the presets see it
+    /// (enabled, with environment overrides applied)
+    pub fn source_component(&self, name: &str) -> Option<ComponentConfig> {
+        self.enabled_components()
+            .into_iter()
+            .find(|(n, c)| n == name && c.mode == ComponentMode::Source)
+            .map(|(_, c)| c)
+    }
+
+    /// bin directory of the linked host that `run` launches, if any
+    pub fn host_bin(&self) -> Option<String> {
+        self.source_component("host")?.bin
+    }
 }
 
 /// Get the current SDK version by reading the junction/symlink target
@@ -113,19 +405,36 @@ pub fn current_version() -> Option<String> {
 
 /// Set the current SDK version
 pub fn set_current_version(version: &str) -> Result<()> {
-    let root = sdk_root();
-    fs::create_dir_all(&root)?;
+    link_current_version(&sdk_root(), version)
+}
+
+/// Point `<root>/current` at `<root>/<version>`
+fn link_current_version(root: &std::path::Path, version: &str) -> Result<()> {
+    fs::create_dir_all(root)?;
 
     // Create "current" junction/symlink so CMAKE_PREFIX_PATH can resolve ~/.mpf-sdk/current
     let link = root.join("current");
-    let target = version_dir(version);
+    let target = root.join(version);
+    if !target.exists() {
+        bail!("SDK version {} not found at {}", version, target.display());
+    }
 
     #[cfg(unix)]
     {
-        if link.exists() || link.is_symlink() {
-            let _ = fs::remove_file(&link);
+        // A real directory (e.g. copied by hand) can't be removed with remove_file
+        if link.is_symlink() {
+            fs::remove_file(&link)
+                .with_context(|| format!("Failed to remove {}", link.display()))?;
+        } else if link.is_dir() {
+            fs::remove_dir_all(&link)
+                .with_context(|| format!("Failed to remove {}", link.display()))?;
+        } else if link.exists() {
+            fs::remove_file(&link)
+                .with_context(|| format!("Failed to remove {}", link.display()))?;
         }
-        let _ = std::os::unix::fs::symlink(&target, &link);
+        std::os::unix::fs::symlink(&target, &link).with_context(|| {
+            format!("Failed to link {} -> {}", link.display(), target.display())
+        })?;
     }
 
     #[cfg(windows)]
@@ -150,27 +459,207 @@ pub fn set_current_version(version: &str) -> Result<()> {
     Ok(())
 }
 
-/// List all installed SDK versions
+/// Remove the "current" junction/symlink, leaving no active SDK version
+pub fn clear_current_version() -> Result<()> {
+    let link = current_link();
+
+    #[cfg(unix)]
+    {
+        if link.is_symlink() {
+            fs::remove_file(&link)
+                .with_context(|| format!("Failed to remove {}", link.display()))?;
+        }
+    }
+
+    #[cfg(windows)]
+    {
+        // Junctions are removed like empty directories; this does not touch the target
+        if link.exists() || fs::read_link(&link).is_ok() {
+            fs::remove_dir(&link)
+                .with_context(|| format!("Failed to remove {}", link.display()))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// What is wrong with a path referenced by dev.json
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathIssueKind {
+    Missing,
+    NotADirectory,
+    NotExecutable,
+}
+
+/// A dev.json path (or the `current` SDK link) that is unusable
+#[derive(Debug, Clone)]
+pub struct ConfigPathIssue {
+    /// Component name, or "sdk" for the `current` link
+    pub component_name: String,
+    pub field_name: &'static str,
+    pub path: String,
+    pub issue_kind: PathIssueKind,
+}
+
+impl std::fmt::Display for ConfigPathIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let problem = match self.issue_kind {
+            PathIssueKind::Missing => "does not exist",
+            PathIssueKind::NotADirectory => "is not a directory",
+            PathIssueKind::NotExecutable => "is not executable",
+        };
+        write!(
+            f,
+            "{}: {} {}: {}",
+            self.component_name, self.field_name, problem, self.path
+        )
+    }
+}
+
+pub fn is_executable(path: &std::path::Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::metadata(path)
+            .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+            .unwrap_or(false)
+    }
+    #[cfg(windows)]
+    {
+        path.is_file()
+    }
+}
+
+/// Check every path of the enabled components and the `current` SDK link.
+///
+/// Linked paths must be existing directories; a linked host's bin directory
+/// must also hold an executable mpf-host. This is the one place such checks
+/// live, so `status`, `env --check` and friends report the same problems.
+pub fn validate_config_paths(config: &DevConfig) -> Vec<ConfigPathIssue> {
+    let mut issues = Vec::new();
+    let mut check_dir = |component: &str, field: &'static str, path: &str| {
+        let p = std::path::Path::new(path);
+        let kind = if !p.exists() {
+            PathIssueKind::Missing
+        } else if !p.is_dir() {
+            PathIssueKind::NotADirectory
+        } else {
+            return;
+        };
+        issues.push(ConfigPathIssue {
+            component_name: component.to_string(),
+            field_name: field,
+            path: path.to_string(),
+            issue_kind: kind,
+        });
+    };
+
+    // exists() follows the link, so a dangling symlink/junction counts as missing
+    let link = current_link();
+    check_dir("sdk", "current", &link.to_string_lossy());
+
+    for (name, comp) in config.enabled_components() {
+        let fields = [
+            ("lib", &comp.lib),
+            ("qml", &comp.qml),
+            ("plugin", &comp.plugin),
+            ("headers", &comp.headers),
+            ("bin", &comp.bin),
+            ("root", &comp.root),
+            ("cmake_config", &comp.cmake_config),
+        ];
+        for (field, value) in fields {
+            if let Some(path) = value {
+                check_dir(&name, field, path);
+            }
+        }
+        for root in &comp.roots {
+            check_dir(&name, "roots", root);
+        }
+    }
+
+    if let Some(bin) = config.host_bin() {
+        let exe = if cfg!(windows) { "mpf-host.exe" } else { "mpf-host" };
+        let host = std::path::Path::new(&bin).join(exe);
+        if std::path::Path::new(&bin).is_dir() {
+            let kind = if !host.exists() {
+                Some(PathIssueKind::Missing)
+            } else if !is_executable(&host) {
+                Some(PathIssueKind::NotExecutable)
+            } else {
+                None
+            };
+            if let Some(kind) = kind {
+                issues.push(ConfigPathIssue {
+                    component_name: "host".to_string(),
+                    field_name: "mpf-host",
+                    path: host.to_string_lossy().to_string(),
+                    issue_kind: kind,
+                });
+            }
+        }
+    }
+
+    issues
+}
+
+/// List all installed SDK versions, newest first.
+///
+/// Semver names are sorted by version (descending); any other names follow
+/// in lexicographic order. Only real directories holding an extracted SDK
+/// (`bin/` or `lib/`) count, so the `current` symlink/junction and
+/// bookkeeping directories like `cache/` are skipped.
 pub fn installed_versions() -> Vec<String> {
     let root = sdk_root();
     if !root.exists() {
         return vec![];
     }
-    
-    fs::read_dir(&root)
+
+    let mut versions: Vec<String> = fs::read_dir(&root)
         .map(|entries| {
             entries
                 .filter_map(|e| e.ok())
-                .filter(|e| e.path().is_dir())
+                .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
                 .filter(|e| {
-                    let name = e.file_name().to_string_lossy().to_string();
-                    // Filter out non-version directories
-                    name.starts_with('v') || name.chars().next().map(|c| c.is_ascii_digit()).unwrap_or(false)
+                    let path = e.path();
+                    path.join("bin").is_dir() || path.join("lib").is_dir()
                 })
                 .map(|e| e.file_name().to_string_lossy().to_string())
                 .collect()
         })
-        .unwrap_or_default()
+        .unwrap_or_default();
+
+    versions.sort_by(|a, b| match (parse_semver(a), parse_semver(b)) {
+        (Some(va), Some(vb)) => vb.cmp(&va),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => a.cmp(b),
+    });
+    versions
+}
+
+/// Whether a version argument is a constraint (`~1.2`, `^1.2.3`, `>=1.0`, ...)
+/// rather than an exact version
+pub fn is_version_constraint(version: &str) -> bool {
+    version.starts_with(['~', '^', '>', '<', '=', '*'])
+}
+
+/// Parse a version name like `v1.2.3`
+pub fn parse_semver(version: &str) -> Option<semver::Version> {
+    semver::Version::parse(version.trim_start_matches('v')).ok()
+}
+
+/// Highest installed version (e.g. `v1.2.4`) satisfying a semver constraint
+pub fn resolve_version_constraint(constraint: &str, installed: &[String]) -> Option<String> {
+    let req = semver::VersionReq::parse(constraint).ok()?;
+    installed
+        .iter()
+        .filter_map(|name| {
+            let version = parse_semver(name)?;
+            req.matches(&version).then_some((version, name))
+        })
+        .max_by(|a, b| a.0.cmp(&b.0))
+        .map(|(_, name)| name.clone())
 }
 
 /// Known MPF components
@@ -184,6 +673,122 @@ pub const KNOWN_COMPONENTS: &[&str] = &[
     "plugin-rules",
 ];
 
+/// Components each known component builds against. Anything not listed
+/// (including custom plugins) depends on the SDK only.
+const COMPONENT_DEPENDENCIES: &[(&str, &[&str])] = &[
+    ("sdk", &[]),
+    ("plugin-orders", &["http-client", "ui-components"]),
+    ("plugin-rules", &["http-client", "ui-components"]),
+];
+
+/// Direct dependencies of a component
+pub fn component_dependencies(name: &str) -> &'static [&'static str] {
+    COMPONENT_DEPENDENCIES
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, deps)| *deps)
+        .unwrap_or(&["sdk"])
+}
+
 pub fn is_known_component(name: &str) -> bool {
     KNOWN_COMPONENTS.contains(&name)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn current_link_points_at_version() {
+        let root = tempfile::tempdir().unwrap();
+        fs::create_dir(root.path().join("v1.0.0")).unwrap();
+        fs::create_dir(root.path().join("v1.1.0")).unwrap();
+        let link = root.path().join("current");
+
+        link_current_version(root.path(), "v1.0.0").unwrap();
+        assert_eq!(fs::read_link(&link).unwrap(), root.path().join("v1.0.0"));
+
+        link_current_version(root.path(), "v1.1.0").unwrap();
+        assert_eq!(fs::read_link(&link).unwrap(), root.path().join("v1.1.0"));
+        assert!(root.path().join("v1.0.0").is_dir());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn current_link_replaces_real_directory() {
+        let root = tempfile::tempdir().unwrap();
+        fs::create_dir(root.path().join("v1.0.0")).unwrap();
+        let link = root.path().join("current");
+        fs::create_dir_all(link.join("lib")).unwrap();
+
+        link_current_version(root.path(), "v1.0.0").unwrap();
+        assert!(link.is_symlink());
+        assert_eq!(fs::read_link(&link).unwrap(), root.path().join("v1.0.0"));
+    }
+
+    #[test]
+    fn current_link_requires_installed_version() {
+        let root = tempfile::tempdir().unwrap();
+        let err = link_current_version(root.path(), "v9.9.9").unwrap_err();
+        assert!(err.to_string().contains("SDK version v9.9.9 not found"));
+    }
+
+    fn component(lib: &str) -> ComponentConfig {
+        ComponentConfig {
+            lib: Some(lib.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn merge_overlay_wins() {
+        let mut base = DevConfig::default();
+        base.components.insert("http-client".into(), component("/base/lib"));
+        base.extra_cmake_vars.insert("BUILD_TESTS".into(), "OFF".into());
+        let mut overlay = DevConfig::default();
+        overlay.components.insert("http-client".into(), component("/overlay/lib"));
+        overlay.extra_cmake_vars.insert("BUILD_TESTS".into(), "ON".into());
+
+        let merged = DevConfig::merge(base, overlay);
+        assert_eq!(merged.components["http-client"].lib.as_deref(), Some("/overlay/lib"));
+        assert_eq!(merged.extra_cmake_vars["BUILD_TESTS"], "ON");
+    }
+
+    #[test]
+    fn merge_keeps_base_when_overlay_is_missing() {
+        let mut base = DevConfig {
+            sdk_version: Some("v1.0.0".into()),
+            ..Default::default()
+        };
+        base.components.insert("host".into(), component("/base/lib"));
+        let overlay = DevConfig {
+            sdk_version: Some("v2.0.0".into()),
+            ..Default::default()
+        };
+
+        let merged = DevConfig::merge(base, overlay);
+        assert_eq!(merged.sdk_version.as_deref(), Some("v1.0.0"));
+        assert_eq!(merged.components["host"].lib.as_deref(), Some("/base/lib"));
+
+        let merged = DevConfig::merge(DevConfig::default(), merged);
+        assert_eq!(merged.sdk_version.as_deref(), Some("v1.0.0"));
+    }
+
+    #[test]
+    fn merge_combines_component_and_cmake_var_maps() {
+        let mut base = DevConfig::default();
+        base.components.insert("host".into(), component("/host/lib"));
+        base.extra_cmake_vars.insert("A".into(), "1".into());
+        let mut overlay = DevConfig::default();
+        overlay.components.insert("http-client".into(), component("/http/lib"));
+        overlay.extra_cmake_vars.insert("B".into(), "2".into());
+
+        let merged = DevConfig::merge(base, overlay);
+        let mut names: Vec<_> = merged.components.keys().cloned().collect();
+        names.sort();
+        assert_eq!(names, ["host", "http-client"]);
+        assert_eq!(merged.extra_cmake_vars["A"], "1");
+        assert_eq!(merged.extra_cmake_vars["B"], "2");
+    }
+}
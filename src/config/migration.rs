@@ -0,0 +1,85 @@
+//! dev.json schema migrations.
+//!
+//! Each step upgrades the raw JSON by one schema version, so a file of any
+//! age reaches the current schema by applying the steps in order.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use super::{DevConfig, CURRENT_SCHEMA_VERSION};
+
+/// Schema version recorded in a raw dev.json (files without one are version 0)
+pub fn schema_version(value: &Value) -> u32 {
+    value
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .unwrap_or(0) as u32
+}
+
+/// Upgrade a raw dev.json from `from_version` to the current schema
+pub fn migrate_config(old: Value, from_version: u32) -> Result<DevConfig> {
+    let mut value = migrate_value(old, from_version);
+    value["schema_version"] = Value::from(CURRENT_SCHEMA_VERSION);
+    serde_json::from_value(value).context("Failed to parse migrated dev.json")
+}
+
+/// Apply the migration steps to the raw JSON
+fn migrate_value(mut value: Value, from_version: u32) -> Value {
+    if from_version < 1 {
+        v0_to_v1(&mut value);
+    }
+    value
+}
+
+/// v0 → v1: the schema only gained `schema_version` itself, so files
+/// written before it load unchanged and just get the version recorded
+fn v0_to_v1(value: &mut Value) {
+    if let Some(fields) = value.as_object_mut() {
+        fields.insert("schema_version".to_string(), Value::from(1));
+    }
+}
+
+/// Flatten JSON into dotted paths of leaf values
+pub(crate) fn flatten(value: &Value, prefix: &str, out: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, child) in map {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten(child, &path, out);
+            }
+        }
+        _ => {
+            out.insert(prefix.to_string(), value.clone());
+        }
+    }
+}
+
+/// Human-readable list of the differences between two dev.json documents
+pub fn describe_changes(old: &Value, new: &Value) -> Vec<String> {
+    let mut before = BTreeMap::new();
+    let mut after = BTreeMap::new();
+    flatten(old, "", &mut before);
+    flatten(new, "", &mut after);
+
+    let mut changes = Vec::new();
+    for (path, value) in &before {
+        match after.get(path) {
+            None => changes.push(format!("- {} (removed)", path)),
+            Some(new_value) if new_value != value => {
+                changes.push(format!("~ {}: {} → {}", path, value, new_value))
+            }
+            Some(_) => {}
+        }
+    }
+    for (path, value) in &after {
+        if !before.contains_key(path) {
+            changes.push(format!("+ {} = {}", path, value));
+        }
+    }
+    changes
+}
@@ -1,7 +1,7 @@
 mod config;
 mod commands;
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use anyhow::Result;
 
 #[derive(Parser)]
@@ -17,18 +17,77 @@ struct Cli {
 enum Commands {
     /// Download and install MPF SDK
     Setup {
-        /// SDK version to install (default: latest)
+        /// SDK version or alias to install (default: latest stable)
         #[arg(short, long)]
         version: Option<String>,
+        /// Install the latest prerelease (recorded as the `nightly` alias)
+        #[arg(long, conflicts_with = "version")]
+        prerelease: bool,
+        /// Download the SDK for this architecture instead of the host's
+        #[arg(long, value_parser = clap::builder::PossibleValuesParser::new(commands::SDK_ARCHES))]
+        arch: Option<String>,
+        /// GitHub token for this run (default: config github-token, then GITHUB_TOKEN)
+        #[arg(long)]
+        token: Option<String>,
+        /// Download this exact release asset (skips OS/arch detection)
+        #[arg(long, conflicts_with = "arch")]
+        asset: Option<String>,
+        /// List the release's assets and sizes instead of installing
+        #[arg(long)]
+        list_assets: bool,
+        /// Retries after a network failure (with 1s, 4s, ... backoff)
+        #[arg(long, default_value_t = 2)]
+        retries: u32,
     },
     
     /// List installed SDK versions
-    Versions,
+    Versions {
+        /// Check GitHub for a newer release than the newest installed one
+        #[arg(long)]
+        outdated: bool,
+        /// Machine-readable JSON output
+        #[arg(long)]
+        json: bool,
+    },
     
     /// Switch to a specific SDK version
     Use {
-        /// Version to use
+        /// Version, alias or constraint (e.g. ~1.2) to use
         version: String,
+        /// Download the version first if it is not installed
+        #[arg(long)]
+        install_if_missing: bool,
+    },
+
+    /// Name an SDK version (e.g. `mpf-dev alias lts v1.0.30`)
+    Alias {
+        /// Alias name
+        #[arg(required_unless_present = "list")]
+        name: Option<String>,
+        /// Version the alias points to
+        #[arg(required_unless_present = "list")]
+        version: Option<String>,
+        /// List all aliases
+        #[arg(long, conflicts_with_all = ["name", "version"])]
+        list: bool,
+    },
+    
+    /// Manage installed SDK versions
+    Sdk {
+        #[command(subcommand)]
+        action: SdkAction,
+    },
+    
+    /// Manage the SDK download cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+    
+    /// Change mpf-dev settings stored in dev.json
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
     },
     
     /// Link a component for source development
@@ -39,28 +98,223 @@ enum Commands {
     
     /// Unregister a component from source development
     Unlink {
-        /// Component name (or "all" to unlink everything)
+        /// Component names (or "all" to unlink everything)
+        #[arg(required = true, num_args = 1.., action = clap::ArgAction::Append)]
+        component: Vec<String>,
+        /// Show what would be removed without changing dev.json
+        #[arg(long)]
+        dry_run: bool,
+        /// Also unlink locked components
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Rename a linked component (e.g. plugin-orders → plugin-sales)
+    Rename {
+        /// Current component name
+        from: String,
+
+        /// New component name
+        to: String,
+    },
+
+    /// Find plugins with an mpf-plugin.json manifest under a directory and link them
+    Discover {
+        /// Directory to scan recursively
+        directory: String,
+
+        /// Link every discovered plugin without asking
+        #[arg(long, short = 'y')]
+        yes: bool,
+
+        /// Also scan this directory on every `run` (saved in dev.json)
+        #[arg(long)]
+        auto: bool,
+    },
+
+    /// Register another project directory that uses a linked component
+    /// (its presets are regenerated on link/unlink too)
+    AddRoot {
+        /// Component name
+        component: String,
+
+        /// Project source directory
+        project_dir: String,
+    },
+
+    /// Lock a component so `unlink` skips it
+    Lock {
+        /// Component name
+        component: String,
+    },
+
+    /// Remove the lock from a component
+    Unlock {
+        /// Component name
+        component: String,
+    },
+
+    /// Temporarily leave a linked component out, keeping its paths
+    Disable {
+        /// Component name
+        component: String,
+    },
+
+    /// Re-enable a disabled component
+    Enable {
+        /// Component name
         component: String,
     },
+
+    /// Create a named environment (dev/staging/prod) of component overrides
+    EnvCreate {
+        /// Environment name
+        name: String,
+
+        /// Start from a copy of the current components
+        #[arg(long)]
+        copy: bool,
+    },
+
+    /// Activate an environment's component overrides (no name: base config)
+    EnvSwitch {
+        /// Environment name
+        name: Option<String>,
+    },
+
+    /// Upgrade dev.json to the current schema version, showing the changes
+    Migrate {
+        /// Show the changes without writing dev.json
+        #[arg(long)]
+        dry_run: bool,
+    },
     
     /// Show current development configuration status
-    Status,
+    Status {
+        /// Order components by name or by most recent link
+        #[arg(long, default_value = "name", value_parser = ["name", "date"])]
+        sort: String,
+        /// Quiet health check for scripts: exit 0 = ok, 1 = warnings, 2 = errors
+        #[arg(long)]
+        check: bool,
+        /// Show components as a dependency tree (source vs SDK-provided)
+        #[arg(long, conflicts_with = "check")]
+        graph: bool,
+    },
     
     /// Print environment variables for manual shell setup
-    Env,
+    Env {
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<String>,
+        /// Output format: shell (export/set lines), dotenv (KEY=value for docker --env-file)
+        /// or python-dotenv (KEY=value with quoting)
+        #[arg(long, value_enum, default_value_t = commands::Format::Shell)]
+        format: commands::Format,
+        /// Overwrite the output file without asking
+        #[arg(long)]
+        force: bool,
+        /// Verify every referenced path exists (non-zero exit if not)
+        #[arg(long)]
+        check: bool,
+        /// Show how the computed variables differ from the current shell
+        #[arg(long)]
+        diff: bool,
+    },
     
     /// Generate CMakeUserPresets.json for current project
     Init {
         /// Also delete the entire build/ directory
         #[arg(long)]
         clean: bool,
+        /// CMake generator for the generated presets
+        #[arg(short = 'G', long, value_parser = clap::builder::PossibleValuesParser::new(commands::CMAKE_GENERATORS))]
+        generator: Option<String>,
+        /// Also write a .clangd file using build/compile_commands.json
+        #[arg(long)]
+        clangd: bool,
+        /// Also write a .clang-tidy file with Qt-friendly default checks
+        #[arg(long)]
+        clang_tidy: bool,
+        /// Also write the IDE-specific config for this IDE
+        #[arg(long, value_parser = ["vscode", "clion", "qtcreator"])]
+        ide: Option<String>,
+        /// Don't set CMAKE_C_COMPILER / CMAKE_CXX_COMPILER in the presets
+        #[arg(long)]
+        no_compiler: bool,
+        /// Set CMAKE_TOOLCHAIN_FILE instead of the detected compilers
+        #[arg(long, value_name = "PATH")]
+        toolchain_file: Option<String>,
+        /// Regenerate presets and clear the CMake cache even if nothing changed
+        #[arg(long)]
+        force: bool,
+        /// Detect Qt and the compilers again instead of using the cached paths
+        #[arg(long)]
+        refresh_cache: bool,
+        /// Use ccache as compiler launcher (remembered for later re-inits)
+        #[arg(long, conflicts_with = "sccache")]
+        ccache: bool,
+        /// Use sccache as compiler launcher (remembered for later re-inits)
+        #[arg(long)]
+        sccache: bool,
+        /// Only enable the presets on this host platform (CMake `condition`)
+        #[arg(long, value_name = "PLATFORM", value_parser = ["windows", "linux", "macos"])]
+        condition_platform: Option<String>,
+        /// Name the presets per platform (dev-windows, dev-linux, ...) and keep
+        /// the other platforms' presets already in the file
+        #[arg(long, conflicts_with = "condition_platform")]
+        multi_platform: bool,
     },
 
     /// Run MPF host with development overrides
+    ///
+    /// For headless CI, pass `--platform offscreen`. On Linux, when no
+    /// DISPLAY/WAYLAND_DISPLAY is available and QT_QPA_PLATFORM is unset,
+    /// offscreen is selected automatically.
     Run {
         /// Enable debug mode
         #[arg(short, long)]
         debug: bool,
+
+        /// Load extra environment variables (KEY=VALUE per line) before launching
+        #[arg(long, value_name = "PATH")]
+        env_file: Option<String>,
+
+        /// Print the launch command and environment instead of running it
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Kill mpf-host after this many seconds (exit code 124)
+        #[arg(long, value_name = "SECONDS")]
+        timeout: Option<u64>,
+
+        /// Redirect mpf-host stdout/stderr to a log file
+        #[arg(long, value_name = "FILE")]
+        capture: Option<String>,
+
+        /// Lines of the captured log to print after exit (with --capture)
+        #[arg(long, value_name = "N", default_value_t = 20, requires = "capture")]
+        tail: usize,
+
+        /// Qt platform plugin (sets QT_QPA_PLATFORM), e.g. offscreen, xcb, wayland
+        #[arg(long)]
+        platform: Option<String>,
+
+        /// Start from an empty environment, keeping only what a Qt app needs
+        #[arg(long)]
+        isolated: bool,
+
+        /// Plugin directory to load for this run only (prepended to MPF_PLUGIN_PATH; repeatable)
+        #[arg(long = "plugin", value_name = "PATH")]
+        extra_plugins: Vec<String>,
+
+        /// QML import path for this run only (prepended to QML_IMPORT_PATH; repeatable)
+        #[arg(long = "qml", value_name = "PATH")]
+        extra_qml: Vec<String>,
+
+        /// Library path for this run only (prepended to the library path; repeatable)
+        #[arg(long = "lib", value_name = "PATH")]
+        extra_libs: Vec<String>,
         
         /// Additional arguments to pass to mpf-host
         #[arg(last = true)]
@@ -72,6 +326,121 @@ enum Commands {
         #[command(subcommand)]
         action: WorkspaceAction,
     },
+
+    /// Print diagnostic information (Markdown) for bug reports
+    DebugInfo,
+}
+
+#[derive(Subcommand)]
+enum SdkAction {
+    /// Remove an installed SDK version
+    Uninstall {
+        /// Version to remove
+        version: String,
+        /// Allow removing the currently active version
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Check that an installed SDK has the expected directory layout
+    Verify {
+        /// Version to verify
+        version: String,
+    },
+
+    /// Show the release notes of an SDK version
+    Changelog {
+        /// Version (default: the active version)
+        version: Option<String>,
+        /// Print the Markdown as-is instead of formatting it
+        #[arg(long)]
+        raw: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheAction {
+    /// List cached SDK archives
+    List,
+
+    /// Remove cached SDK archives
+    Clear {
+        /// Only remove archives for this version
+        #[arg(short, long)]
+        version: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Print a value by dotted key (sdk_version, components.<name>.lib, ...)
+    Get {
+        /// Setting key
+        key: String,
+    },
+
+    /// Set a value by dotted key (post-link-hook, components.<name>.lib, ...)
+    Set {
+        /// Setting key
+        key: String,
+        /// New value (empty string clears it)
+        value: String,
+    },
+
+    /// List every setting in dev.json as `key = value`
+    List,
+
+    /// Extra CMake cache variables injected into generated presets
+    CmakeVar {
+        #[command(subcommand)]
+        action: CmakeVarAction,
+    },
+
+    /// Forget the cached Qt/compiler paths so they are detected again
+    ClearCache,
+}
+
+#[derive(Subcommand)]
+enum CmakeVarAction {
+    /// Add or change a variable (e.g. BUILD_TESTS ON)
+    Set {
+        /// Variable name
+        key: String,
+        /// Value
+        value: String,
+    },
+
+    /// Remove a variable
+    Unset {
+        /// Variable name
+        key: String,
+    },
+}
+
+/// Options shared by every `link` subcommand
+#[derive(Args, Clone, Default)]
+pub struct LinkFlags {
+    /// Print what changed in dev.json for this component
+    #[arg(long)]
+    pub diff: bool,
+
+    /// Save and re-init even if the component is already linked with the same paths
+    #[arg(long)]
+    pub force: bool,
+
+    /// Fail instead of warning when the QML path contains no qmldir
+    /// (or a linked plugin has no shared libraries)
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Path priority; higher values come first in library/QML/plugin paths
+    #[arg(long, allow_hyphen_values = true)]
+    pub priority: Option<i32>,
+
+    /// CMake package config directory for non-standard install trees
+    /// (added to CMAKE_PREFIX_PATH instead of inferring it from the lib path)
+    #[arg(long, value_name = "DIR")]
+    pub cmake_config: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -82,12 +451,16 @@ enum LinkAction {
         name: String,
         /// Path to plugin build output directory
         path: String,
+        #[command(flatten)]
+        flags: LinkFlags,
     },
     
     /// Link the host build output (auto-derives bin, qml paths)
     Host {
         /// Path to host build output directory
         path: String,
+        #[command(flatten)]
+        flags: LinkFlags,
     },
     
     /// Link a library component (ui-components, http-client, etc.)
@@ -96,12 +469,19 @@ enum LinkAction {
         name: String,
         /// Path to component build output directory
         path: String,
+        #[command(flatten)]
+        flags: LinkFlags,
     },
 
     /// Link a local SDK install for development (overrides ~/.mpf-sdk/current)
     Sdk {
         /// Path to SDK cmake install prefix (must contain lib/cmake/MPF/)
         path: String,
+        /// Don't warn when the local SDK's major version differs from the installed SDK
+        #[arg(long)]
+        ignore_version: bool,
+        #[command(flatten)]
+        flags: LinkFlags,
     },
     
     /// Link with manual path specification (advanced)
@@ -123,6 +503,8 @@ enum LinkAction {
         /// Path to bin directory
         #[arg(long)]
         bin: Option<String>,
+        #[command(flatten)]
+        flags: LinkFlags,
     },
 }
 
@@ -133,6 +515,26 @@ enum WorkspaceAction {
         /// Workspace directory (default: current directory)
         #[arg(short, long)]
         path: Option<String>,
+
+        /// Don't generate the VS Code .code-workspace file
+        #[arg(long)]
+        no_vscode: bool,
+
+        /// Clone over SSH (git@github.com:...) instead of HTTPS
+        #[arg(long)]
+        ssh: bool,
+
+        /// Clone from this GitHub organization instead of QMPF (e.g. your fork)
+        #[arg(long, value_name = "ORG")]
+        fork_org: Option<String>,
+
+        /// Only write workspace files; don't clone any repos
+        #[arg(long)]
+        no_clone: bool,
+
+        /// CMake generator for CMakePresets.json and builds (default: Xcode on macOS, Ninja elsewhere)
+        #[arg(short = 'G', long, value_parser = clap::builder::PossibleValuesParser::new(commands::CMAKE_GENERATORS))]
+        generator: Option<String>,
     },
     
     /// Build all components in workspace
@@ -140,6 +542,38 @@ enum WorkspaceAction {
         /// Build type: Debug or Release
         #[arg(short, long, default_value = "Debug")]
         config: String,
+
+        /// Build only this CMake target
+        #[arg(short, long)]
+        target: Option<String>,
+
+        /// Number of parallel build jobs
+        #[arg(short, long)]
+        jobs: Option<usize>,
+
+        /// Show full compiler invocations
+        #[arg(long)]
+        verbose: bool,
+
+        /// CMake preset used for both configure and build
+        #[arg(long)]
+        preset: Option<String>,
+
+        /// CMake generator used when configuring without a preset
+        #[arg(short = 'G', long, value_parser = clap::builder::PossibleValuesParser::new(commands::CMAKE_GENERATORS))]
+        generator: Option<String>,
+
+        /// Install the build into the active SDK (~/.mpf-sdk/current) afterwards
+        #[arg(long)]
+        install: bool,
+
+        /// Build components one at a time and print a per-component timing table
+        #[arg(long)]
+        summary: bool,
+
+        /// Compile and link with --coverage (for `workspace coverage`)
+        #[arg(long)]
+        coverage: bool,
     },
     
     /// Run mpf-host from workspace
@@ -150,7 +584,101 @@ enum WorkspaceAction {
     },
     
     /// Show workspace status
-    Status,
+    Status {
+        /// Print machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show uncommitted changes across all workspace repos
+    Diff {
+        /// Show a diffstat instead of the full diff
+        #[arg(long)]
+        stat: bool,
+    },
+
+    /// Run clang-tidy over the sources of all workspace components
+    Lint {
+        /// Apply clang-tidy's suggested fixes (--fix-errors)
+        #[arg(long)]
+        fix: bool,
+
+        /// Only lint files whose workspace-relative path contains this text
+        #[arg(long)]
+        filter: Option<String>,
+    },
+
+    /// Run clang-format over the sources of all workspace components
+    Format {
+        /// Only report files that need formatting; exit non-zero if any do
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// Run tests of a coverage build and report line coverage per component
+    Coverage {
+        /// Also write an HTML report to build/coverage/
+        #[arg(long)]
+        html: bool,
+
+        /// Exit non-zero if total line coverage is below this percentage
+        #[arg(long, value_parser = clap::value_parser!(u8).range(0..=100))]
+        threshold: Option<u8>,
+    },
+
+    /// Switch all workspace repos to the same branch
+    Checkout {
+        /// Branch name
+        branch: String,
+        /// Create the branch where it doesn't exist yet
+        #[arg(short = 'b', long)]
+        create: bool,
+    },
+
+    /// Archive the workspace into a zip file (skips .git, build and ignored files)
+    Export {
+        /// Output zip file
+        #[arg(short, long)]
+        output: String,
+        /// Also include build/ artifacts
+        #[arg(long)]
+        include_build: bool,
+        /// Only include repo sources, not the generated workspace files
+        #[arg(long)]
+        repos_only: bool,
+    },
+
+    /// Check out each repo at the commit matching the active SDK release
+    Sync,
+
+    /// Clone an extra repository into the workspace and add it to the build
+    Add {
+        /// Git clone URL
+        repo_url: String,
+        /// Directory name (default: derived from the URL)
+        name: Option<String>,
+    },
+
+    /// Remove a repository from the workspace build
+    Remove {
+        /// Repository directory name (e.g. mpf-plugin-rules)
+        name: String,
+        /// Keep the cloned directory on disk
+        #[arg(long)]
+        keep_source: bool,
+    },
+
+    /// Inspect workspace repositories
+    Repos {
+        #[command(subcommand)]
+        action: ReposAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum ReposAction {
+    /// List built-in and custom repositories
+    List,
 }
 
 #[tokio::main]
@@ -158,20 +686,230 @@ async fn main() -> Result<()> {
     let cli = Cli::parse();
     
     match cli.command {
-        Commands::Setup { version } => commands::setup(version).await,
-        Commands::Versions => commands::versions(),
-        Commands::Use { version } => commands::use_version(&version),
-        Commands::Link { action } => commands::link_action(action),
-        Commands::Unlink { component } => commands::unlink(&component),
-        Commands::Status => commands::status(),
-        Commands::Env => commands::env_vars(),
-        Commands::Init { clean } => commands::init(clean),
-        Commands::Run { debug, args } => commands::run(debug, args),
+        Commands::Setup {
+            version,
+            prerelease,
+            arch,
+            token,
+            asset,
+            list_assets,
+            retries,
+        } => {
+            commands::setup(commands::SetupOptions {
+                version,
+                prerelease,
+                arch,
+                token,
+                asset,
+                list_assets,
+                retries,
+            })
+            .await
+        }
+        Commands::Versions { outdated, json } => commands::versions(outdated, json).await,
+        Commands::Use {
+            version,
+            install_if_missing,
+        } => commands::use_version(&version, install_if_missing).await,
+        Commands::Alias { list: true, .. } => commands::alias_list(),
+        Commands::Alias { name, version, .. } => {
+            commands::alias_set(&name.unwrap_or_default(), &version.unwrap_or_default())
+        }
+        Commands::Sdk { action } => match action {
+            SdkAction::Uninstall { version, force } => commands::sdk_uninstall(&version, force),
+            SdkAction::Verify { version } => commands::sdk_verify(&version),
+            SdkAction::Changelog { version, raw } => {
+                commands::show_changelog(version.as_deref(), raw).await
+            }
+        },
+        Commands::Cache { action } => match action {
+            CacheAction::List => commands::cache_list(),
+            CacheAction::Clear { version } => commands::cache_clear(version),
+        },
+        Commands::Config { action } => match action {
+            ConfigAction::Get { key } => commands::config_get(&key),
+            ConfigAction::Set { key, value } => commands::config_set(&key, &value),
+            ConfigAction::List => commands::config_list(),
+            ConfigAction::CmakeVar { action } => match action {
+                CmakeVarAction::Set { key, value } => commands::cmake_var_set(&key, &value),
+                CmakeVarAction::Unset { key } => commands::cmake_var_unset(&key),
+            },
+            ConfigAction::ClearCache => commands::config_clear_cache(),
+        },
+        Commands::Link { action } => commands::link_action(action).await,
+        Commands::Unlink {
+            component,
+            dry_run,
+            force,
+        } => {
+            let names: Vec<&str> = component.iter().map(String::as_str).collect();
+            commands::unlink(&names, dry_run, force)
+        }
+        Commands::Rename { from, to } => commands::rename_component(&from, &to),
+        Commands::Discover {
+            directory,
+            yes,
+            auto,
+        } => commands::discover(&directory, yes, auto),
+        Commands::AddRoot {
+            component,
+            project_dir,
+        } => commands::add_root(&component, &project_dir),
+        Commands::Lock { component } => commands::set_locked(&component, true),
+        Commands::Unlock { component } => commands::set_locked(&component, false),
+        Commands::Disable { component } => commands::set_enabled(&component, false),
+        Commands::Enable { component } => commands::set_enabled(&component, true),
+        Commands::EnvCreate { name, copy } => commands::env_create(&name, copy),
+        Commands::EnvSwitch { name } => commands::env_switch(name.as_deref()),
+        Commands::Migrate { dry_run } => commands::migrate(dry_run),
+        Commands::Status { sort, check, graph } => {
+            if check {
+                commands::status_check()
+            } else {
+                commands::status(&sort, graph)
+            }
+        }
+        Commands::Env {
+            output,
+            format,
+            force,
+            check,
+            diff,
+        } => commands::env_vars(output, format, force, check, diff),
+        Commands::Init {
+            clean,
+            generator,
+            clangd,
+            clang_tidy,
+            ide,
+            no_compiler,
+            toolchain_file,
+            force,
+            refresh_cache,
+            ccache,
+            sccache,
+            condition_platform,
+            multi_platform,
+        } => commands::init(commands::InitOptions {
+            clean,
+            generator,
+            clangd,
+            clang_tidy,
+            ide,
+            no_compiler,
+            toolchain_file,
+            force,
+            refresh_cache,
+            condition_platform,
+            multi_platform,
+            compiler_cache: if sccache {
+                Some(config::CompilerCache::Sccache)
+            } else if ccache {
+                Some(config::CompilerCache::Ccache)
+            } else {
+                None
+            },
+        }),
+        Commands::Run {
+            debug,
+            env_file,
+            dry_run,
+            timeout,
+            capture,
+            tail,
+            platform,
+            isolated,
+            extra_plugins,
+            extra_qml,
+            extra_libs,
+            args,
+        } => {
+            commands::run(commands::RunOptions {
+                debug,
+                env_file,
+                dry_run,
+                timeout,
+                capture,
+                tail,
+                platform,
+                isolated,
+                extra_plugins,
+                extra_qml,
+                extra_libs,
+                args,
+            })
+            .await
+        }
         Commands::Workspace { action } => match action {
-            WorkspaceAction::Init { path } => commands::workspace_init(path),
-            WorkspaceAction::Build { config } => commands::workspace_build(&config),
+            WorkspaceAction::Init {
+                path,
+                no_vscode,
+                ssh,
+                fork_org,
+                no_clone,
+                generator,
+            } => commands::workspace_init(
+                path,
+                no_vscode,
+                ssh,
+                fork_org.as_deref(),
+                no_clone,
+                generator,
+            ),
+            WorkspaceAction::Build {
+                config,
+                target,
+                jobs,
+                verbose,
+                preset,
+                generator,
+                install,
+                summary,
+                coverage,
+            } => commands::workspace_build(commands::BuildOptions {
+                config,
+                target,
+                jobs,
+                verbose,
+                preset,
+                generator,
+                install,
+                summary,
+                coverage,
+            }),
             WorkspaceAction::Run { args } => commands::workspace_run(args),
-            WorkspaceAction::Status => commands::workspace_status(),
+            WorkspaceAction::Status { json } => commands::workspace_status(json),
+            WorkspaceAction::Diff { stat } => commands::workspace_diff(stat),
+            WorkspaceAction::Lint { fix, filter } => {
+                commands::workspace_lint(fix, filter.as_deref())
+            }
+            WorkspaceAction::Format { check } => commands::workspace_format(check),
+            WorkspaceAction::Coverage { html, threshold } => {
+                commands::workspace_coverage(html, threshold)
+            }
+            WorkspaceAction::Checkout { branch, create } => {
+                commands::workspace_checkout(&branch, create)
+            }
+            WorkspaceAction::Export {
+                output,
+                include_build,
+                repos_only,
+            } => commands::workspace_export(
+                std::path::Path::new(&output),
+                include_build,
+                repos_only,
+            ),
+            WorkspaceAction::Sync => commands::workspace_sync().await,
+            WorkspaceAction::Add { repo_url, name } => {
+                commands::workspace_add(&repo_url, name.as_deref())
+            }
+            WorkspaceAction::Remove { name, keep_source } => {
+                commands::workspace_remove(&name, keep_source)
+            }
+            WorkspaceAction::Repos { action } => match action {
+                ReposAction::List => commands::workspace_repos_list(),
+            },
         },
+        Commands::DebugInfo => commands::debug_info(),
     }
 }